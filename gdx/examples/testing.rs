@@ -1,6 +1,6 @@
 use gdx::{View, view};
-use godot::classes::{Button, Label};
 
+#[allow(dead_code)]
 fn blob() -> impl View<(Option<i32>, (f32, f32))> + use<> {
     view! {
         become (a, b: (f32, f32)) => (a, &mut b.0) {
@@ -1,18 +1,32 @@
-use std::{collections::VecDeque, marker::PhantomData, sync::Arc};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use gdx_macro::view;
+#[cfg(feature = "serde")]
+use godot::{builtin::PackedInt64Array, meta::ToGodot, obj::InstanceId};
 use godot::{
     classes::Node,
-    global::godot_print,
     obj::{Gd, Inherits},
 };
 use parking_lot::Mutex;
 
 use crate::{
-    Context, View,
+    Context, HistoryHandle, Message, MessageResult, View,
+    ctx::FullMessage,
+    history::{History, HistoryAction, HistoryOp, SnapshotFns},
+    inspect::{Inspector, ViewTreeDump},
+    pool::NodePool,
+    stats::{Monitors, RunStats},
+    trace::{trace_event, trace_span},
     view::{AnchorType, ArgTuple},
 };
 
+const DEFAULT_COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
 pub struct GDXApp<State: ArgTuple, AppView: View<State>, AppFn: FnMut(&mut State) -> AppView> {
     state: State,
     view: Option<(AppView, AppView::ViewState)>,
@@ -20,6 +34,9 @@ pub struct GDXApp<State: ArgTuple, AppView: View<State>, AppFn: FnMut(&mut State
 
     root: Gd<Node>,
     ctx: Context,
+    history: Option<History<State>>,
+    coalesce_window: Duration,
+    monitors: Option<Monitors>,
 
     _p: PhantomData<AppView>,
 }
@@ -41,12 +58,276 @@ where
             ctx: Context {
                 id_counter: 0,
                 path: vec![],
+                // messages never leave the main thread, the queue is only shared with callables
+                #[allow(clippy::arc_with_non_send_sync)]
                 msg_queue: Arc::new(Mutex::new(VecDeque::new())),
+                send_queue: Arc::new(Mutex::new(VecDeque::new())),
                 needs_rebuild: false,
+                keep_nodes: false,
+                node_pool: None,
+                tree_cursor: None,
+                item_cursor: None,
+                history: Default::default(),
+                stats: Default::default(),
+                executor: Default::default(),
             },
+            history: None,
+            coalesce_window: DEFAULT_COALESCE_WINDOW,
+            monitors: None,
             _p: PhantomData,
         }
     }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+    /// Mutable access to the state from outside the view tree, schedules a rebuild.
+    pub fn state_mut(&mut self) -> &mut State {
+        self.ctx.needs_rebuild = true;
+        &mut self.state
+    }
+    pub fn root(&self) -> Gd<Node> {
+        self.root.clone()
+    }
+    /// Whether a [`App::run`] would do nothing, i.e. the view is built, no messages are queued
+    /// and no rebuild is pending.
+    pub fn is_idle(&self) -> bool {
+        self.view.is_some()
+            && !self.ctx.needs_rebuild
+            && self.ctx.msg_queue.lock().is_empty()
+            && self.ctx.send_queue.lock().is_empty()
+    }
+    /// Whether any spawned task hasn't finished yet, they're polled once per [`App::run`].
+    pub fn has_pending_tasks(&self) -> bool {
+        !self.ctx.executor.is_empty()
+    }
+
+    /// Parks the nodes of torn down elements, up to `limit` per node type, and reuses them
+    /// for elements of the same type instead of allocating. Helps with keyed lists that churn a lot.
+    pub fn with_node_pool(mut self, limit: usize) -> Self {
+        self.ctx.node_pool = Some(NodePool::new(limit));
+        self
+    }
+
+    /// How close together coalesced batches have to be to merge, 500ms by default.
+    /// Can be set before or after enabling history.
+    pub fn with_coalesce_window(mut self, window: Duration) -> Self {
+        self.coalesce_window = window;
+        if let Some(history) = &mut self.history {
+            history.coalesce_window = window;
+        }
+        self
+    }
+
+    pub fn history_handle(&self) -> HistoryHandle {
+        HistoryHandle {
+            queue: self.ctx.msg_queue.clone(),
+            status: self.ctx.history.clone(),
+        }
+    }
+    pub fn can_undo(&self) -> bool {
+        self.history.as_ref().is_some_and(|h| h.can_undo())
+    }
+    pub fn can_redo(&self) -> bool {
+        self.history.as_ref().is_some_and(|h| h.can_redo())
+    }
+    pub fn undo(&mut self) {
+        if let Some(history) = &mut self.history
+            && history.undo(&mut self.state)
+        {
+            self.ctx.needs_rebuild = true;
+        }
+    }
+    pub fn redo(&mut self) {
+        if let Some(history) = &mut self.history
+            && history.redo(&mut self.state)
+        {
+            self.ctx.needs_rebuild = true;
+        }
+    }
+
+    /// Counters collected during the last [`App::run`].
+    pub fn stats(&self) -> &RunStats {
+        &self.ctx.stats
+    }
+    /// Pushes the stats of every run into `Performance` custom monitors named `{prefix}/{counter}`,
+    /// so they show up in the editor debugger.
+    /// The monitors are removed again when the app is dropped.
+    pub fn register_monitors(&mut self, prefix: &str) {
+        // the old ones have to go first, the prefix might be the same
+        self.monitors = None;
+        self.monitors = Some(Monitors::register(prefix));
+    }
+    pub fn unregister_monitors(&mut self) {
+        self.monitors = None;
+    }
+
+    /// Walks the live view tree, returns an empty dump if the view hasn't been built yet.
+    pub fn inspect(&self) -> ViewTreeDump {
+        let mut inspector = Inspector::new();
+        if let Some((view, state)) = &self.view {
+            view.inspect(state, &mut inspector);
+        }
+        inspector.finish()
+    }
+    /// The top-level nodes the view built under the root, in order.
+    pub fn nodes(&self) -> Vec<Gd<Node>> {
+        let mut nodes = vec![];
+        if let Some((view, state)) = &self.view {
+            view.collect_nodes(state, &mut nodes);
+        }
+        nodes
+    }
+
+    fn apply_history_action(&mut self, action: HistoryAction) {
+        match action {
+            HistoryAction::Undo => self.undo(),
+            HistoryAction::Redo => self.redo(),
+            HistoryAction::Coalesce(key) => {
+                if let Some(history) = &mut self.history {
+                    history.coalesce(key);
+                }
+            }
+            HistoryAction::Record(op) => {
+                if let Some(history) = &mut self.history {
+                    history.record(op);
+                }
+            }
+        }
+        if let Some(history) = &mut self.history {
+            history.begin_batch(&self.state);
+        }
+    }
+}
+
+fn downcast_op<State: 'static>(op: Box<dyn Any>) -> Option<HistoryOp<State>> {
+    op.downcast().ok().map(|op| *op)
+}
+
+impl<State: ArgTuple + 'static, AppView, AppFn> GDXApp<State, AppView, AppFn>
+where
+    AppView: View<State>,
+    AppFn: FnMut(&mut State) -> AppView,
+{
+    /// Records a snapshot of the state for every handled message batch that changed it,
+    /// keeping at most `limit` entries.
+    pub fn with_history(mut self, limit: usize) -> Self
+    where
+        State: Clone + PartialEq,
+    {
+        self.history = Some(History::new(
+            limit,
+            self.coalesce_window,
+            Some(SnapshotFns {
+                clone: State::clone,
+                eq: State::eq,
+            }),
+            downcast_op::<State>,
+            self.ctx.history.clone(),
+        ));
+        self
+    }
+    /// Only records the inverse operations passed to [`HistoryHandle::record`], without snapshotting.
+    pub fn with_op_history(mut self, limit: usize) -> Self {
+        self.history = Some(History::new(
+            limit,
+            self.coalesce_window,
+            None,
+            downcast_op::<State>,
+            self.ctx.history.clone(),
+        ));
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+const STATE_META: &str = "__gdx_state";
+#[cfg(feature = "serde")]
+const NODES_META: &str = "__gdx_nodes";
+
+#[cfg(feature = "serde")]
+impl<State: ArgTuple, AppView, AppFn> GDXApp<State, AppView, AppFn>
+where
+    State: serde::Serialize,
+    AppView: View<State>,
+    AppFn: FnMut(&mut State) -> AppView,
+{
+    /// Serializes the state to JSON, to be handed back to [`GDXApp::restore_state`].
+    pub fn save_state(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.state)
+    }
+
+    /// Stashes the serialized state and the built top-level nodes as metadata on the root node,
+    /// so that a reloaded library can pick them up with [`GDXApp::restore_from_root`].
+    /// Call this before the library is unloaded, e.g. from `ExtensionLibrary::on_level_deinit`.
+    pub fn save_to_root(&mut self) -> serde_json::Result<()> {
+        let json = self.save_state()?;
+        self.root.set_meta(STATE_META, &json.to_variant());
+
+        if let Some((view, state)) = &self.view {
+            let mut nodes = vec![];
+            view.collect_nodes(state, &mut nodes);
+            let ids = nodes
+                .iter()
+                .map(|node| node.instance_id().to_i64())
+                .collect::<PackedInt64Array>();
+            self.root.set_meta(NODES_META, &ids.to_variant());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<State: ArgTuple, AppView, AppFn> GDXApp<State, AppView, AppFn>
+where
+    State: serde::de::DeserializeOwned,
+    AppView: View<State>,
+    AppFn: FnMut(&mut State) -> AppView,
+{
+    pub fn restore_state(&mut self, json: &str) -> serde_json::Result<()> {
+        self.state = serde_json::from_str(json)?;
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+        self.ctx.needs_rebuild = true;
+        Ok(())
+    }
+
+    /// Restores state saved by [`GDXApp::save_to_root`] and frees the nodes built before the reload,
+    /// so the next [`App::run`] builds the view from the restored state under the same root.
+    /// Returns `false` if the root has nothing saved.
+    pub fn restore_from_root(&mut self) -> serde_json::Result<bool> {
+        if !self.root.has_meta(STATE_META) {
+            return Ok(false);
+        }
+        // the meta might have been set by something else, e.g. a script or an older build
+        let json = self.root.get_meta(STATE_META).try_to::<String>();
+        self.root.remove_meta(STATE_META);
+
+        if self.root.has_meta(NODES_META) {
+            let ids = self.root.get_meta(NODES_META).try_to::<PackedInt64Array>();
+            self.root.remove_meta(NODES_META);
+            for id in ids.as_ref().map_or(&[][..], |ids| ids.as_slice()) {
+                let Some(id) = InstanceId::try_from_i64(*id) else {
+                    continue;
+                };
+                if let Ok(mut node) = Gd::<Node>::try_from_instance_id(id) {
+                    if let Some(mut parent) = node.get_parent() {
+                        parent.remove_child(&node);
+                    }
+                    node.queue_free();
+                }
+            }
+        }
+
+        let json = json.map_err(|err| {
+            <serde_json::Error as serde::de::Error>::custom(format!(
+                "saved state isn't a string: {err}"
+            ))
+        })?;
+        self.restore_state(&json)?;
+        Ok(true)
+    }
 }
 
 pub trait App {
@@ -59,18 +340,66 @@ where
     AppFn: FnMut(&mut State) -> AppView,
 {
     fn run(&mut self) {
-        if let Some((prev, state)) = &mut self.view {
+        self.ctx.stats = RunStats::default();
+        if self.view.is_some() {
+            self.ctx.stats.tasks_finished = self.ctx.executor.poll() as u32;
+            {
+                let mut send_queue = self.ctx.send_queue.lock();
+                self.ctx
+                    .msg_queue
+                    .lock()
+                    .extend(send_queue.drain(..).map(|msg| FullMessage {
+                        msg: Message::Proxy { value: msg.value },
+                        path: msg.path,
+                    }));
+            }
             if !{ self.ctx.msg_queue.lock().is_empty() } {
+                trace_span!(debug_span, "gdx::messages");
+                #[cfg(feature = "tracing")]
+                let start = Instant::now();
+
                 self.ctx.needs_rebuild = true;
-                while let Some(v) = self.ctx.msg_queue.lock().pop_front() {
-                    prev.message(v.msg, &v.path, state, &mut self.state);
+                if let Some(history) = &mut self.history {
+                    history.begin_batch(&self.state);
                 }
+                while let Some(v) = { self.ctx.msg_queue.lock().pop_front() } {
+                    match v.msg {
+                        Message::History(action) => self.apply_history_action(action),
+                        msg => {
+                            let (prev, state) = self.view.as_mut().unwrap();
+                            let result = prev.message(msg, &v.path, state, &mut self.state);
+                            trace_event!(
+                                trace,
+                                path = ?v.path,
+                                handled = !matches!(result, MessageResult::Stale(_)),
+                                "dispatched message"
+                            );
+                            match result {
+                                MessageResult::Success => {
+                                    self.ctx.stats.messages_handled += 1;
+                                    if let Some(history) = &mut self.history {
+                                        history.touch();
+                                    }
+                                }
+                                MessageResult::Untracked => self.ctx.stats.messages_handled += 1,
+                                MessageResult::Stale(_) => self.ctx.stats.messages_stale += 1,
+                            }
+                        }
+                    }
+                }
+                if let Some(history) = &mut self.history {
+                    history.commit_batch(&self.state);
+                }
+                trace_event!(debug, elapsed = ?start.elapsed(), "handled messages");
             }
+            let (prev, state) = self.view.as_mut().unwrap();
             while self.ctx.needs_rebuild {
+                trace_span!(debug_span, "gdx::rebuild");
+                let start = Instant::now();
+
                 self.ctx.needs_rebuild = false;
 
                 let new = (self.app_fn)(&mut self.state);
-                godot_print!("Rebuilding");
                 new.rebuild(
                     prev,
                     state,
@@ -80,10 +409,14 @@ where
                     &mut self.state,
                 );
                 *prev = new;
+                self.ctx.stats.rebuild_time += start.elapsed();
+                trace_event!(debug, elapsed = ?start.elapsed(), "rebuilt");
             }
         } else {
+            trace_span!(debug_span, "gdx::build");
+            let start = Instant::now();
+
             let view = (self.app_fn)(&mut self.state);
-            godot_print!("Initial build");
             let state = view.build(
                 &mut self.ctx,
                 &mut self.root,
@@ -91,6 +424,11 @@ where
                 &mut self.state,
             );
             self.view = Some((view, state));
+            self.ctx.stats.build_time = start.elapsed();
+            trace_event!(debug, elapsed = ?start.elapsed(), "initial build");
+        }
+        if let Some(monitors) = &self.monitors {
+            *monitors.shared.lock() = self.ctx.stats;
         }
     }
 }
@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use godot::{
+    builtin::{GString, Rect2, Variant, Vector2},
+    classes::{CanvasItem, Control, IControl, INode, INode2D, InputEvent, Node, Node2D, Object},
+    obj::{Base, Gd, Inherits, WithBaseField},
+    prelude::GodotClass,
+    register::godot_api,
+};
+
+pub(crate) type InputHandler = Box<dyn Fn(&Gd<InputEvent>) -> bool>;
+pub(crate) type CanDropFn = Rc<dyn Fn(Vector2, &Variant) -> bool>;
+pub(crate) type TooltipFn = Rc<dyn Fn(GString) -> Option<Gd<Control>>>;
+
+/// Hands `unhandled_input` to a closure, the event is marked as handled if it returns true.
+#[derive(GodotClass)]
+#[class(base=Node, init)]
+pub struct GdxInputListener {
+    pub(crate) handler: Option<InputHandler>,
+    base: Base<Node>,
+}
+
+#[godot_api]
+impl INode for GdxInputListener {
+    fn unhandled_input(&mut self, event: Gd<InputEvent>) {
+        if let Some(handler) = &self.handler
+            && handler(&event)
+            && let Some(mut viewport) = self.base().get_viewport()
+        {
+            viewport.set_input_as_handled();
+        }
+    }
+}
+
+/// Closures the virtuals of [`GdxControl`] and [`GdxNode2D`] dispatch to,
+/// falling back to what godot would do when a slot is empty.
+#[derive(Default)]
+pub struct Overrides {
+    pub(crate) get_drag_data: Option<Rc<dyn Fn(Vector2) -> Variant>>,
+    pub(crate) can_drop_data: Option<CanDropFn>,
+    pub(crate) drop_data: Option<Rc<dyn Fn(Vector2, Variant)>>,
+    pub(crate) make_custom_tooltip: Option<TooltipFn>,
+    pub(crate) has_point: Option<Rc<dyn Fn(Vector2) -> bool>>,
+    pub(crate) get_minimum_size: Option<Rc<dyn Fn() -> Vector2>>,
+    pub(crate) draw: Option<Rc<dyn Fn(Gd<CanvasItem>)>>,
+}
+
+/// A gdx class whose virtuals can be overridden by element modifiers.
+pub trait VirtualHost: GodotClass + Inherits<Node> + Inherits<CanvasItem> {
+    fn with_overrides<R>(this: &mut Gd<Self>, f: impl FnOnce(&mut Overrides) -> R) -> R;
+}
+
+#[derive(GodotClass)]
+#[class(base=Control, init)]
+pub struct GdxControl {
+    overrides: Overrides,
+    base: Base<Control>,
+}
+
+impl VirtualHost for GdxControl {
+    fn with_overrides<R>(this: &mut Gd<Self>, f: impl FnOnce(&mut Overrides) -> R) -> R {
+        f(&mut this.bind_mut().overrides)
+    }
+}
+
+#[godot_api]
+impl IControl for GdxControl {
+    fn get_drag_data(&mut self, at_position: Vector2) -> Variant {
+        match self.overrides.get_drag_data.clone() {
+            Some(f) => f(at_position),
+            None => Variant::nil(),
+        }
+    }
+    fn can_drop_data(&self, at_position: Vector2, data: Variant) -> bool {
+        self.overrides
+            .can_drop_data
+            .as_ref()
+            .is_some_and(|f| f(at_position, &data))
+    }
+    fn drop_data(&mut self, at_position: Vector2, data: Variant) {
+        if let Some(f) = self.overrides.drop_data.clone() {
+            f(at_position, data);
+        }
+    }
+    fn make_custom_tooltip(&self, for_text: GString) -> Option<Gd<Object>> {
+        self.overrides
+            .make_custom_tooltip
+            .as_ref()
+            .and_then(|f| f(for_text))
+            .map(|control| control.upcast())
+    }
+    fn has_point(&self, point: Vector2) -> bool {
+        match &self.overrides.has_point {
+            Some(f) => f(point),
+            None => Rect2::new(Vector2::ZERO, self.base().get_size()).contains_point(point),
+        }
+    }
+    fn get_minimum_size(&self) -> Vector2 {
+        match &self.overrides.get_minimum_size {
+            Some(f) => f(),
+            None => Vector2::ZERO,
+        }
+    }
+    fn draw(&mut self) {
+        if let Some(f) = self.overrides.draw.clone() {
+            f(self.to_gd().upcast());
+        }
+    }
+}
+
+/// Only dispatches `draw`, the other virtuals are `Control`'s.
+#[derive(GodotClass)]
+#[class(base=Node2D, init)]
+pub struct GdxNode2D {
+    overrides: Overrides,
+    base: Base<Node2D>,
+}
+
+impl VirtualHost for GdxNode2D {
+    fn with_overrides<R>(this: &mut Gd<Self>, f: impl FnOnce(&mut Overrides) -> R) -> R {
+        f(&mut this.bind_mut().overrides)
+    }
+}
+
+#[godot_api]
+impl INode2D for GdxNode2D {
+    fn draw(&mut self) {
+        if let Some(f) = self.overrides.draw.clone() {
+            f(self.to_gd().upcast());
+        }
+    }
+}
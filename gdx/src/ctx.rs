@@ -3,15 +3,33 @@ use std::{any::Any, collections::VecDeque, rc::Rc, sync::Arc};
 use godot::builtin::Variant;
 use parking_lot::Mutex;
 
-use crate::view::ViewID;
+use crate::{
+    executor::Executor,
+    history::{HistoryAction, HistoryStatus},
+    pool::NodePool,
+    stats::RunStats,
+    view::{ViewID, items::ItemCursor, tree::TreeCursor},
+};
 
 pub type MsgQueue = Arc<Mutex<VecDeque<FullMessage>>>;
+pub type SendMsgQueue = Arc<Mutex<VecDeque<SendMessage>>>;
 pub struct Context {
     pub(crate) id_counter: u64,
     pub(crate) path: Vec<ViewID>,
 
     pub(crate) msg_queue: MsgQueue,
+    pub(crate) send_queue: SendMsgQueue,
     pub(crate) needs_rebuild: bool,
+    /// Set while tearing down a subtree whose root node lingers for an exit transition,
+    /// elements inside it leave their nodes alone instead of removing and freeing them.
+    pub(crate) keep_nodes: bool,
+    pub(crate) node_pool: Option<NodePool>,
+    pub(crate) tree_cursor: Option<TreeCursor>,
+    pub(crate) item_cursor: Option<ItemCursor>,
+
+    pub(crate) history: Arc<Mutex<HistoryStatus>>,
+    pub(crate) stats: RunStats,
+    pub(crate) executor: Rc<Executor>,
 }
 
 impl Context {
@@ -20,6 +38,10 @@ impl Context {
         self.id_counter += 1;
         out
     }
+    /// Whether torn down elements park their nodes, so modifiers have to undo what they did to them.
+    pub(crate) fn pooling(&self) -> bool {
+        self.node_pool.is_some() && !self.keep_nodes
+    }
     pub(crate) fn with_id<R>(&mut self, id: ViewID, f: impl FnOnce(&mut Self) -> R) -> R {
         self.path.push(id);
         let out = f(self);
@@ -33,6 +55,12 @@ pub struct FullMessage {
     pub(crate) msg: Message,
     pub(crate) path: Arc<[ViewID]>,
 }
+/// A proxy message posted from another thread, moved into the main queue at the start of [`App::run`](crate::App::run).
+#[derive(Debug)]
+pub struct SendMessage {
+    pub(crate) value: Box<dyn Any + Send>,
+    pub(crate) path: Arc<[ViewID]>,
+}
 #[derive(Debug)]
 pub enum Message {
     Signal {
@@ -43,9 +71,12 @@ pub enum Message {
     Proxy {
         value: Box<dyn Any>,
     },
+    History(HistoryAction),
 }
 pub enum MessageResult {
     Success,
+    /// Handled, but not a change worth its own history entry, e.g. per-frame updates.
+    Untracked,
     // Nop,
     Stale(Message),
 }
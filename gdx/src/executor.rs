@@ -0,0 +1,133 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    task::{Context, Waker},
+};
+
+struct Task {
+    owner: u64,
+    fut: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+/// Polls every pending task once per [`App::run`](crate::App::run). There is no real waker,
+/// so the futures have to be fine with being polled a couple of extra times.
+#[derive(Default)]
+pub(crate) struct Executor {
+    tasks: RefCell<Vec<Task>>,
+    owner_counter: Cell<u64>,
+}
+
+impl Executor {
+    pub(crate) fn new_owner(&self) -> u64 {
+        let out = self.owner_counter.get();
+        self.owner_counter.set(out + 1);
+        out
+    }
+
+    pub(crate) fn spawn(&self, owner: u64, fut: impl Future<Output = ()> + 'static) {
+        self.tasks.borrow_mut().push(Task {
+            owner,
+            fut: Box::pin(fut),
+        });
+    }
+
+    pub(crate) fn cancel(&self, owner: u64) -> usize {
+        let mut tasks = self.tasks.borrow_mut();
+        let before = tasks.len();
+        tasks.retain(|task| task.owner != owner);
+        before - tasks.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tasks.borrow().is_empty()
+    }
+
+    /// Returns how many tasks finished.
+    pub(crate) fn poll(&self) -> usize {
+        // taken out so tasks can spawn more tasks while being polled
+        let mut tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+        let before = tasks.len();
+        let mut cx = Context::from_waker(Waker::noop());
+        tasks.retain_mut(|task| task.fut.as_mut().poll(&mut cx).is_pending());
+        let finished = before - tasks.len();
+
+        let mut current = self.tasks.borrow_mut();
+        tasks.append(&mut current);
+        *current = tasks;
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{rc::Rc, task::Poll};
+
+    /// Pending on its first poll, done on the second.
+    async fn yield_once() {
+        let mut yielded = false;
+        std::future::poll_fn(|_| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    #[test]
+    fn poll_runs_ready_tasks_to_completion() {
+        let executor = Executor::default();
+        let done = Rc::new(Cell::new(false));
+        let flag = done.clone();
+        executor.spawn(0, async move { flag.set(true) });
+
+        assert!(!done.get());
+        assert_eq!(executor.poll(), 1);
+        assert!(done.get());
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn pending_tasks_are_polled_again() {
+        let executor = Executor::default();
+        executor.spawn(0, yield_once());
+
+        assert_eq!(executor.poll(), 0);
+        assert!(!executor.is_empty());
+        assert_eq!(executor.poll(), 1);
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn tasks_can_spawn_tasks_while_polled() {
+        let executor = Rc::new(Executor::default());
+        let inner = executor.clone();
+        executor.spawn(0, async move { inner.spawn(0, async {}) });
+
+        assert_eq!(executor.poll(), 1);
+        assert!(!executor.is_empty());
+        assert_eq!(executor.poll(), 1);
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn cancel_only_drops_the_owners_tasks() {
+        let executor = Executor::default();
+        let a = executor.new_owner();
+        let b = executor.new_owner();
+        assert_ne!(a, b);
+        executor.spawn(a, yield_once());
+        executor.spawn(a, yield_once());
+        executor.spawn(b, yield_once());
+
+        assert_eq!(executor.cancel(a), 2);
+        assert_eq!(executor.cancel(a), 0);
+        assert_eq!(executor.poll(), 0);
+        assert_eq!(executor.poll(), 1);
+        assert!(executor.is_empty());
+    }
+}
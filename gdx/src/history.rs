@@ -0,0 +1,430 @@
+use std::{
+    any::Any,
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use godot::global::godot_error;
+use parking_lot::Mutex;
+
+pub(crate) struct HistoryOp<State> {
+    pub(crate) undo: Box<dyn Fn(&mut State)>,
+    pub(crate) redo: Box<dyn Fn(&mut State)>,
+}
+
+#[derive(Debug)]
+pub enum HistoryAction {
+    Undo,
+    Redo,
+    Coalesce(u64),
+    Record(Box<dyn Any>),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HistoryStatus {
+    pub(crate) can_undo: bool,
+    pub(crate) can_redo: bool,
+}
+
+enum HistoryEntry<State> {
+    Snapshot(State),
+    Ops(Vec<HistoryOp<State>>),
+}
+
+/// How snapshot history copies the state and tells whether a batch changed it.
+pub(crate) struct SnapshotFns<State> {
+    pub(crate) clone: fn(&State) -> State,
+    pub(crate) eq: fn(&State, &State) -> bool,
+}
+
+struct PendingBatch<State> {
+    snapshot: Option<State>,
+    ops: Vec<HistoryOp<State>>,
+    coalesce: Option<u64>,
+    // only tracked handlers count, per-frame updates and the like leave the batch alone
+    touched: bool,
+}
+
+pub(crate) struct History<State> {
+    undo_stack: VecDeque<HistoryEntry<State>>,
+    redo_stack: Vec<HistoryEntry<State>>,
+    pending: Option<PendingBatch<State>>,
+    last_coalesce: Option<(u64, Instant)>,
+
+    pub(crate) limit: usize,
+    pub(crate) coalesce_window: Duration,
+    snapshot_fns: Option<SnapshotFns<State>>,
+    downcast_op: fn(Box<dyn Any>) -> Option<HistoryOp<State>>,
+
+    status: Arc<Mutex<HistoryStatus>>,
+}
+
+impl<State> History<State> {
+    pub(crate) fn new(
+        limit: usize,
+        coalesce_window: Duration,
+        snapshot_fns: Option<SnapshotFns<State>>,
+        downcast_op: fn(Box<dyn Any>) -> Option<HistoryOp<State>>,
+        status: Arc<Mutex<HistoryStatus>>,
+    ) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            pending: None,
+            last_coalesce: None,
+            limit,
+            coalesce_window,
+            snapshot_fns,
+            downcast_op,
+            status,
+        }
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub(crate) fn begin_batch(&mut self, state: &State) {
+        if self.pending.is_none() {
+            self.pending = Some(PendingBatch {
+                snapshot: self.snapshot_fns.as_ref().map(|fns| (fns.clone)(state)),
+                ops: vec![],
+                coalesce: None,
+                touched: false,
+            });
+        }
+    }
+    /// A handler ran that might have changed the state, whether it did is checked on commit.
+    pub(crate) fn touch(&mut self) {
+        if let Some(pending) = &mut self.pending {
+            pending.touched = true;
+        }
+    }
+    pub(crate) fn coalesce(&mut self, key: u64) {
+        if let Some(pending) = &mut self.pending {
+            pending.coalesce = Some(key);
+        }
+    }
+    pub(crate) fn record(&mut self, op: Box<dyn Any>) {
+        let Some(op) = (self.downcast_op)(op) else {
+            godot_error!("HistoryHandle::record was called with a state type other than the app's");
+            return;
+        };
+        if let Some(pending) = &mut self.pending {
+            pending.ops.push(op);
+        }
+    }
+
+    pub(crate) fn commit_batch(&mut self, state: &State) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        let changed = match (&pending.snapshot, &self.snapshot_fns) {
+            (Some(snapshot), Some(fns)) => pending.touched && !(fns.eq)(snapshot, state),
+            _ => false,
+        };
+        if !changed && pending.ops.is_empty() && pending.coalesce.is_none() {
+            return;
+        }
+        let entry = if !pending.ops.is_empty() {
+            HistoryEntry::Ops(pending.ops)
+        } else if let Some(snapshot) = pending.snapshot {
+            HistoryEntry::Snapshot(snapshot)
+        } else {
+            return;
+        };
+
+        let now = Instant::now();
+        let coalesced = match (pending.coalesce, self.last_coalesce) {
+            (Some(key), Some((last_key, at))) => {
+                key == last_key && now.duration_since(at) <= self.coalesce_window
+            }
+            _ => false,
+        };
+        self.last_coalesce = pending.coalesce.map(|key| (key, now));
+
+        match (coalesced, entry, self.undo_stack.back_mut()) {
+            (true, HistoryEntry::Ops(ops), Some(HistoryEntry::Ops(top))) => top.extend(ops),
+            (true, HistoryEntry::Snapshot(_), Some(HistoryEntry::Snapshot(_))) => {}
+            (_, entry, _) => {
+                self.undo_stack.push_back(entry);
+                if self.undo_stack.len() > self.limit {
+                    self.undo_stack.pop_front();
+                }
+            }
+        }
+        self.redo_stack.clear();
+        self.update_status();
+    }
+
+    pub(crate) fn undo(&mut self, state: &mut State) -> bool {
+        self.commit_batch(state);
+        let Some(entry) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        let entry = match entry {
+            HistoryEntry::Snapshot(mut snapshot) => {
+                std::mem::swap(state, &mut snapshot);
+                HistoryEntry::Snapshot(snapshot)
+            }
+            HistoryEntry::Ops(ops) => {
+                for op in ops.iter().rev() {
+                    (op.undo)(state);
+                }
+                HistoryEntry::Ops(ops)
+            }
+        };
+        self.redo_stack.push(entry);
+        self.last_coalesce = None;
+        self.update_status();
+        true
+    }
+
+    pub(crate) fn redo(&mut self, state: &mut State) -> bool {
+        self.commit_batch(state);
+        let Some(entry) = self.redo_stack.pop() else {
+            return false;
+        };
+        let entry = match entry {
+            HistoryEntry::Snapshot(mut snapshot) => {
+                std::mem::swap(state, &mut snapshot);
+                HistoryEntry::Snapshot(snapshot)
+            }
+            HistoryEntry::Ops(ops) => {
+                for op in &ops {
+                    (op.redo)(state);
+                }
+                HistoryEntry::Ops(ops)
+            }
+        };
+        self.undo_stack.push_back(entry);
+        self.last_coalesce = None;
+        self.update_status();
+        true
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.pending = None;
+        self.last_coalesce = None;
+        self.update_status();
+    }
+
+    fn update_status(&self) {
+        *self.status.lock() = HistoryStatus {
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn downcast_op(op: Box<dyn Any>) -> Option<HistoryOp<i32>> {
+        op.downcast().ok().map(|op| *op)
+    }
+
+    fn snapshots(limit: usize) -> History<i32> {
+        History::new(
+            limit,
+            Duration::from_secs(60),
+            Some(SnapshotFns {
+                clone: i32::clone,
+                eq: i32::eq,
+            }),
+            downcast_op,
+            Default::default(),
+        )
+    }
+
+    fn edit(history: &mut History<i32>, state: &mut i32, value: i32) {
+        history.begin_batch(state);
+        *state = value;
+        history.touch();
+        history.commit_batch(state);
+    }
+
+    #[test]
+    fn snapshot_undo_redo() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        edit(&mut history, &mut state, 1);
+        edit(&mut history, &mut state, 2);
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 0);
+        assert!(!history.undo(&mut state));
+
+        assert!(history.redo(&mut state));
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 2);
+        assert!(!history.redo(&mut state));
+    }
+
+    #[test]
+    fn clean_batch_records_nothing() {
+        let mut history = snapshots(10);
+        history.begin_batch(&0);
+        history.commit_batch(&0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn handled_batch_without_changes_records_nothing() {
+        let mut history = snapshots(10);
+        edit(&mut history, &mut 0, 0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn untouched_changes_record_nothing() {
+        let mut history = snapshots(10);
+        history.begin_batch(&0);
+        history.commit_batch(&1);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn undo_requested_by_a_handler() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        edit(&mut history, &mut state, 1);
+
+        // the handler asking for it leaves the state alone
+        history.begin_batch(&state);
+        history.touch();
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 0);
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 1);
+    }
+
+    #[test]
+    fn undo_commits_pending_changes_first() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        history.begin_batch(&state);
+        state = 1;
+        history.touch();
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 0);
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 1);
+    }
+
+    #[test]
+    fn commit_clears_redo() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        edit(&mut history, &mut state, 1);
+        history.undo(&mut state);
+        assert!(history.can_redo());
+
+        edit(&mut history, &mut state, 2);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn limit_drops_oldest() {
+        let mut history = snapshots(2);
+        let mut state = 0;
+        for value in 1..=3 {
+            edit(&mut history, &mut state, value);
+        }
+        assert!(history.undo(&mut state));
+        assert!(history.undo(&mut state));
+        assert!(!history.undo(&mut state));
+        assert_eq!(state, 1);
+    }
+
+    #[test]
+    fn coalesced_batches_merge() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        for value in 1..=3 {
+            history.begin_batch(&state);
+            history.coalesce(7);
+            state = value;
+            history.touch();
+            history.commit_batch(&state);
+        }
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn different_coalesce_keys_stay_separate() {
+        let mut history = snapshots(10);
+        let mut state = 0;
+        for (key, value) in [(1, 1), (2, 2)] {
+            history.begin_batch(&state);
+            history.coalesce(key);
+            state = value;
+            history.touch();
+            history.commit_batch(&state);
+        }
+        history.undo(&mut state);
+        assert_eq!(state, 1);
+    }
+
+    #[test]
+    fn recorded_ops_replay() {
+        let mut history = History::new(10, Duration::ZERO, None, downcast_op, Default::default());
+        let mut state = 0;
+        for _ in 0..2 {
+            history.begin_batch(&state);
+            state += 5;
+            history.record(Box::new(HistoryOp::<i32> {
+                undo: Box::new(|s| *s -= 5),
+                redo: Box::new(|s| *s += 5),
+            }));
+            history.commit_batch(&state);
+        }
+        history.undo(&mut state);
+        assert_eq!(state, 5);
+        history.undo(&mut state);
+        assert_eq!(state, 0);
+        history.redo(&mut state);
+        assert_eq!(state, 5);
+    }
+
+    #[test]
+    fn ops_history_ignores_plain_changes() {
+        let mut history = History::new(10, Duration::ZERO, None, downcast_op, Default::default());
+        edit(&mut history, &mut 0, 1);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn status_follows_stacks() {
+        let status = Arc::new(Mutex::new(HistoryStatus::default()));
+        let mut history = History::new(
+            10,
+            Duration::ZERO,
+            Some(SnapshotFns {
+                clone: i32::clone,
+                eq: i32::eq,
+            }),
+            downcast_op,
+            status.clone(),
+        );
+        let mut state = 0;
+        edit(&mut history, &mut state, 1);
+        assert!(status.lock().can_undo);
+        history.undo(&mut state);
+        let status = *status.lock();
+        assert!(!status.can_undo && status.can_redo);
+    }
+}
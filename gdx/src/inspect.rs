@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+use godot::{classes::Node, obj::Gd};
+
+use crate::ViewID;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeDump {
+    pub class: String,
+    pub name: String,
+    pub instance_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewDump {
+    pub type_name: String,
+    pub path: Vec<ViewID>,
+    pub nodes: Vec<NodeDump>,
+    pub attrs: Vec<(String, String)>,
+    pub signals: Vec<String>,
+    pub children: Vec<ViewDump>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ViewTreeDump {
+    pub roots: Vec<ViewDump>,
+}
+
+pub struct Inspector {
+    path: Vec<ViewID>,
+    stack: Vec<Vec<ViewDump>>,
+}
+
+impl Inspector {
+    pub(crate) fn new() -> Self {
+        Self {
+            path: vec![],
+            stack: vec![vec![]],
+        }
+    }
+    pub(crate) fn finish(mut self) -> ViewTreeDump {
+        ViewTreeDump {
+            roots: self.stack.pop().unwrap(),
+        }
+    }
+
+    pub fn with_id<R>(&mut self, id: ViewID, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.path.push(id);
+        let out = f(self);
+        self.path.pop();
+        out
+    }
+
+    pub fn view(
+        &mut self,
+        type_name: impl Into<String>,
+        nodes: &[Gd<Node>],
+        children: impl FnOnce(&mut Self),
+    ) {
+        self.stack.push(vec![]);
+        children(self);
+        let children = self.stack.pop().unwrap();
+        self.stack.last_mut().unwrap().push(ViewDump {
+            type_name: type_name.into(),
+            path: self.path.clone(),
+            nodes: nodes
+                .iter()
+                .map(|node| NodeDump {
+                    class: node.get_class().to_string(),
+                    name: node.get_name().to_string(),
+                    instance_id: node.instance_id().to_i64(),
+                })
+                .collect(),
+            attrs: vec![],
+            signals: vec![],
+            children,
+        });
+    }
+
+    fn last(&mut self) -> Option<&mut ViewDump> {
+        self.stack.last_mut().unwrap().last_mut()
+    }
+    pub fn attr(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        if let Some(last) = self.last() {
+            last.attrs.push((name.into(), value.into()));
+        }
+    }
+    pub fn signal(&mut self, name: impl Into<String>) {
+        if let Some(last) = self.last() {
+            last.signals.push(name.into());
+        }
+    }
+}
+
+/// Strips the module paths out of a type name, `gdx::view::Foo<godot::classes::Button>` becomes `Foo<Button>`.
+pub(crate) fn short_type_name<T: ?Sized>() -> String {
+    let full = std::any::type_name::<T>();
+    let mut out = String::new();
+    let mut segment = String::new();
+    let mut chars = full.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            out.push_str(&segment);
+            segment.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(&segment);
+    out
+}
+
+fn fmt_path(path: &[ViewID]) -> String {
+    let segments = path
+        .iter()
+        .map(|id| match id {
+            ViewID::Structural(v) => format!("S{v}"),
+            ViewID::Key(v) => format!("K{v:x}"),
+        })
+        .collect::<Vec<_>>();
+    format!("/{}", segments.join("/"))
+}
+
+fn json_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl ViewDump {
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"type\":");
+        json_str(out, &self.type_name);
+        out.push_str(",\"path\":");
+        json_str(out, &fmt_path(&self.path));
+        out.push_str(",\"nodes\":[");
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"class\":");
+            json_str(out, &node.class);
+            out.push_str(",\"name\":");
+            json_str(out, &node.name);
+            write!(out, ",\"instance_id\":{}}}", node.instance_id).unwrap();
+        }
+        out.push_str("],\"attrs\":{");
+        for (i, (name, value)) in self.attrs.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json_str(out, name);
+            out.push(':');
+            json_str(out, value);
+        }
+        out.push_str("},\"signals\":[");
+        for (i, signal) in self.signals.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json_str(out, signal);
+        }
+        out.push_str("],\"children\":[");
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            child.write_json(out);
+        }
+        out.push_str("]}");
+    }
+
+    fn write_text(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        write!(out, "{indent}{} {}", self.type_name, fmt_path(&self.path)).unwrap();
+        for node in &self.nodes {
+            write!(out, " {}({}, #{})", node.class, node.name, node.instance_id).unwrap();
+        }
+        out.push('\n');
+        for (name, value) in &self.attrs {
+            writeln!(out, "{indent}  .{name} = {value}").unwrap();
+        }
+        for signal in &self.signals {
+            writeln!(out, "{indent}  @{signal}").unwrap();
+        }
+        for child in &self.children {
+            child.write_text(out, depth + 1);
+        }
+    }
+}
+
+impl ViewTreeDump {
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, root) in self.roots.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            root.write_json(&mut out);
+        }
+        out.push(']');
+        out
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for root in &self.roots {
+            root.write_text(&mut out, 0);
+        }
+        out
+    }
+}
@@ -2,12 +2,24 @@
 #![allow(clippy::too_many_arguments, clippy::single_match)]
 
 mod app;
+mod classes;
 mod ctx;
+mod executor;
+mod history;
+mod inspect;
+mod pool;
+mod stats;
+pub mod testing;
+mod trace;
 mod util;
 mod view;
 
 pub use app::{App, GDXApp};
-pub use ctx::{Context, Message, MessageResult};
+pub use classes::{GdxControl, GdxInputListener, GdxNode2D, Overrides, VirtualHost};
+pub use ctx::{Context, Message, MessageResult, SendMessage};
+pub use history::HistoryAction;
+pub use inspect::{Inspector, NodeDump, ViewDump, ViewTreeDump};
+pub use stats::RunStats;
 
 pub use either;
 pub use gdx_macro::view;
@@ -17,10 +29,15 @@ pub use view::{
     either::EitherViewState,
     element::{
         Element, ElementView, ElementViewState,
+        animated_attr::{AnimatedAttr, AnimatedAttrViewState},
         attr::{Attr, AttrViewState},
+        bind_open::{BindOpen, BindOpenViewState},
+        drag::{Draggable, DraggableViewState, DropTarget, DropTargetViewState},
         el,
         on_build::{OnBuild, OnBuildViewState},
+        on_input::{InputFilter, OnHover, OnHoverViewState, OnInput, OnInputViewState},
         on_mounted::{OnMounted, OnMountedViewState},
+        on_process::{OnProcess, OnProcessViewState},
         on_rebuild::{OnRebuild, OnRebuildViewState},
         on_signal::{OnSignal, OnSignalViewState},
         on_teardown::{OnTeardown, OnTeardownViewState},
@@ -29,10 +46,24 @@ pub use view::{
             ThemeOverrideFontSize, ThemeOverrideIcon, ThemeOverrideStylebox, ThemeOverrideType,
             ThemeOverrideViewState,
         },
+        transition::{
+            Transition, TransitionPhase, TweenSpec, WithTransition, WithTransitionViewState, tween,
+        },
+        virtuals::{OnDropData, OnDropDataViewState, Virtual, VirtualFn, VirtualViewState},
     },
+    history::{HistoryHandle, WithHistory, history},
+    input::{InputListener, InputListenerViewState, on_action, shortcut},
+    items::{ItemHost, ListItem, ListItems, ListItemsViewState, list_item},
     iter::VecViewState,
+    keep_alive::{KeepAlive, KeepAliveViewState, keep_alive},
     map::{MapState, map},
+    menu::{ContextMenu, ContextMenuViewState, SubMenu, SubMenuViewState, submenu},
     option::OptionViewState,
-    proxy::{MessageProxy, Proxy, proxy},
+    proxy::{MessageProxy, Proxy, SendMessageProxy, proxy},
+    router::{Navigator, Router, RouterViewState, router},
+    task::{TaskSpawner, Tasks, TasksViewState, tasks},
+    timer::{TimerView, TimerViewState, interval, timeout},
+    tree::{TreeItemView, TreeItemViewState, TreeItems, TreeItemsViewState, tree_item},
     using::{Using, using},
+    virtual_list::{RowHeight, VirtualList, VirtualListViewState, virtual_list},
 };
@@ -0,0 +1,52 @@
+use std::{any::TypeId, collections::HashMap};
+
+use godot::{
+    classes::Node,
+    obj::{Gd, Inherits},
+};
+
+/// Nodes parked by torn down elements, handed out again to the next element of the same type.
+/// Modifiers put back what they changed, including half-finished tweens, before a node is parked.
+pub(crate) struct NodePool {
+    limit: usize,
+    nodes: HashMap<TypeId, Vec<Gd<Node>>>,
+}
+
+impl NodePool {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn take<N: Inherits<Node>>(&mut self) -> Option<Gd<N>> {
+        let nodes = self.nodes.get_mut(&TypeId::of::<N>())?;
+        while let Some(node) = nodes.pop() {
+            if node.is_instance_valid() {
+                return Some(node.cast());
+            }
+        }
+        None
+    }
+
+    /// Hands the node back if the pool for its type is full.
+    pub(crate) fn park<N: Inherits<Node>>(&mut self, node: Gd<N>) -> Result<(), Gd<N>> {
+        let nodes = self.nodes.entry(TypeId::of::<N>()).or_default();
+        if nodes.len() >= self.limit {
+            return Err(node);
+        }
+        nodes.push(node.upcast());
+        Ok(())
+    }
+}
+
+impl Drop for NodePool {
+    fn drop(&mut self) {
+        for node in self.nodes.drain().flat_map(|(_, nodes)| nodes) {
+            if node.is_instance_valid() {
+                node.free();
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::{sync::Arc, time::Duration};
+
+use godot::{
+    builtin::{Callable, StringName},
+    classes::Performance,
+    obj::Singleton,
+};
+use parking_lot::Mutex;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunStats {
+    pub nodes_created: u32,
+    pub nodes_freed: u32,
+    pub nodes_moved: u32,
+    pub nodes_pooled: u32,
+    pub nodes_reused: u32,
+    pub attrs_written: u32,
+    pub signals_connected: u32,
+    pub signals_disconnected: u32,
+    pub messages_handled: u32,
+    pub messages_stale: u32,
+    pub tasks_finished: u32,
+    pub tasks_cancelled: u32,
+    pub build_time: Duration,
+    pub rebuild_time: Duration,
+}
+
+type MonitorGetter = fn(&RunStats) -> f64;
+
+const MONITORS: &[(&str, MonitorGetter)] = &[
+    ("nodes_created", |s| s.nodes_created as f64),
+    ("nodes_freed", |s| s.nodes_freed as f64),
+    ("nodes_moved", |s| s.nodes_moved as f64),
+    ("nodes_pooled", |s| s.nodes_pooled as f64),
+    ("nodes_reused", |s| s.nodes_reused as f64),
+    ("attrs_written", |s| s.attrs_written as f64),
+    ("signals_connected", |s| s.signals_connected as f64),
+    ("signals_disconnected", |s| s.signals_disconnected as f64),
+    ("messages_handled", |s| s.messages_handled as f64),
+    ("messages_stale", |s| s.messages_stale as f64),
+    ("tasks_finished", |s| s.tasks_finished as f64),
+    ("tasks_cancelled", |s| s.tasks_cancelled as f64),
+    ("build_time_ms", |s| s.build_time.as_secs_f64() * 1000.0),
+    ("rebuild_time_ms", |s| s.rebuild_time.as_secs_f64() * 1000.0),
+];
+
+pub(crate) struct Monitors {
+    prefix: String,
+    pub(crate) shared: Arc<Mutex<RunStats>>,
+}
+
+impl Monitors {
+    fn id(&self, name: &str) -> StringName {
+        format!("{}/{}", self.prefix, name).as_str().into()
+    }
+
+    pub(crate) fn register(prefix: &str) -> Self {
+        let monitors = Self {
+            prefix: prefix.into(),
+            shared: Default::default(),
+        };
+        let mut perf = Performance::singleton();
+        for (name, get) in MONITORS {
+            let id = monitors.id(name);
+            if perf.has_custom_monitor(&id) {
+                perf.remove_custom_monitor(&id);
+            }
+            let shared = monitors.shared.clone();
+            let get = *get;
+            perf.add_custom_monitor(
+                &id,
+                &Callable::from_fn("gdx_monitor", move |_| get(&shared.lock())),
+            );
+        }
+        monitors
+    }
+
+    fn unregister(&self) {
+        let mut perf = Performance::singleton();
+        for (name, _) in MONITORS {
+            let id = self.id(name);
+            if perf.has_custom_monitor(&id) {
+                perf.remove_custom_monitor(&id);
+            }
+        }
+    }
+}
+
+impl Drop for Monitors {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
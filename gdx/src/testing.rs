@@ -0,0 +1,360 @@
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+use godot::{
+    builtin::{Variant, VariantType},
+    classes::{Engine, Node, SceneTree},
+    meta::ToGodot,
+    obj::{Gd, GodotClass, Inherits, InstanceId, NewAlloc, Singleton},
+};
+
+use crate::{App, ArgTuple, GDXApp, View, ViewDump, ViewID, ViewTreeDump};
+
+pub enum Query {
+    Class(String),
+    Name(String),
+    Path(Vec<ViewID>),
+    Instance(InstanceId),
+}
+impl Query {
+    pub fn class<N: GodotClass>() -> Self {
+        Query::Class(N::class_id().to_string())
+    }
+    pub fn name(name: impl Into<String>) -> Self {
+        Query::Name(name.into())
+    }
+    pub fn path(path: impl Into<Vec<ViewID>>) -> Self {
+        Query::Path(path.into())
+    }
+    pub fn node<N: Inherits<Node>>(node: &Gd<N>) -> Self {
+        Query::Instance(node.instance_id())
+    }
+}
+
+/// Drives a [`GDXApp`] under its own root node, meant for tests running under `godot --headless`.
+pub struct TestApp<State: ArgTuple, AppView: View<State>, AppFn: FnMut(&mut State) -> AppView> {
+    app: GDXApp<State, AppView, AppFn>,
+    owns_root: bool,
+    pub max_runs: usize,
+}
+
+impl<State: ArgTuple, AppView, AppFn> TestApp<State, AppView, AppFn>
+where
+    AppView: View<State>,
+    AppFn: FnMut(&mut State) -> AppView,
+{
+    /// Builds the app under a fresh root node, added to the scene tree if there is one.
+    pub fn new(state: State, app_fn: AppFn) -> Self {
+        let root = Node::new_alloc();
+        if let Some(tree) = Engine::singleton()
+            .get_main_loop()
+            .and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok())
+            && let Some(mut tree_root) = tree.get_root()
+        {
+            tree_root.add_child(&root);
+        }
+        let mut out = Self::with_root(root, state, app_fn);
+        out.owns_root = true;
+        out
+    }
+
+    pub fn with_root<N: Inherits<Node>>(root: Gd<N>, state: State, app_fn: AppFn) -> Self {
+        let mut out = Self {
+            app: GDXApp::new(root, state, app_fn),
+            owns_root: false,
+            max_runs: 100,
+        };
+        out.pump();
+        out
+    }
+
+    pub fn app(&self) -> &GDXApp<State, AppView, AppFn> {
+        &self.app
+    }
+    pub fn app_mut(&mut self) -> &mut GDXApp<State, AppView, AppFn> {
+        &mut self.app
+    }
+    pub fn root(&self) -> Gd<Node> {
+        self.app.root()
+    }
+    pub fn state(&self) -> &State {
+        self.app.state()
+    }
+    /// Mutates the state from outside and pumps the app afterwards.
+    pub fn update(&mut self, f: impl FnOnce(&mut State)) {
+        f(self.app.state_mut());
+        self.pump();
+    }
+
+    /// Runs the app until it is idle, returns how many runs it took.
+    pub fn pump(&mut self) -> usize {
+        let mut runs = 0;
+        loop {
+            self.app.run();
+            runs += 1;
+            if self.app.is_idle() {
+                return runs;
+            }
+            if runs >= self.max_runs {
+                panic!("App still wasn't idle after {runs} runs");
+            }
+        }
+    }
+
+    pub fn find(&self, query: &Query) -> Vec<Gd<Node>> {
+        match query {
+            Query::Path(path) => {
+                fn walk(views: &[ViewDump], path: &[ViewID], out: &mut Vec<Gd<Node>>) {
+                    for view in views {
+                        if view.path == path {
+                            out.extend(view.nodes.iter().filter_map(|node| {
+                                Gd::try_from_instance_id(InstanceId::from_i64(node.instance_id))
+                                    .ok()
+                            }));
+                        }
+                        walk(&view.children, path, out);
+                    }
+                }
+                let mut out = vec![];
+                walk(&self.app.inspect().roots, path, &mut out);
+                out
+            }
+            _ => {
+                fn walk(node: &Gd<Node>, query: &Query, out: &mut Vec<Gd<Node>>) {
+                    for child in node.get_children().iter_shared() {
+                        let matches = match query {
+                            Query::Class(class) => child.is_class(class.as_str()),
+                            Query::Name(name) => child.get_name().to_string() == *name,
+                            Query::Instance(id) => child.instance_id() == *id,
+                            Query::Path(_) => unreachable!(),
+                        };
+                        if matches {
+                            out.push(child.clone());
+                        }
+                        walk(&child, query, out);
+                    }
+                }
+                let mut out = vec![];
+                walk(&self.root(), query, &mut out);
+                out
+            }
+        }
+    }
+
+    /// Finds exactly one node matching the query, panics if there's none or more than one.
+    pub fn get<N: Inherits<Node>>(&self, query: &Query) -> Gd<N> {
+        let mut found = self.find(query);
+        match found.len() {
+            1 => {
+                found.pop().unwrap().try_cast().unwrap_or_else(|node| {
+                    panic!("Found {node:?}, which isn't a {}", N::class_id())
+                })
+            }
+            0 => panic!("Found no node"),
+            n => panic!("Found {n} nodes, expected one"),
+        }
+    }
+
+    /// Emits a signal on the node as if the user did it and pumps the app.
+    pub fn emit<N: Inherits<Node>>(&mut self, node: &Gd<N>, signal: &str, args: &[Variant]) {
+        let mut node = node.clone().upcast::<Node>();
+        node.emit_signal(signal, args);
+        self.pump();
+    }
+
+    pub fn assert_prop<N: Inherits<Node>>(&self, node: &Gd<N>, name: &str, expected: impl ToGodot) {
+        let node = node.clone().upcast::<Node>();
+        let actual = node.get(name);
+        let expected = expected.to_variant();
+        assert_eq!(
+            actual, expected,
+            "Property `{name}` of {node:?} is {actual}, expected {expected}"
+        );
+    }
+
+    /// Serializes the node tree the view built, see [`snapshot`].
+    pub fn snapshot(&self) -> String {
+        snapshot(&self.app.nodes(), &self.app.inspect())
+    }
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        assert_snapshot(&self.snapshot(), path);
+    }
+}
+
+impl<State: ArgTuple, AppView, AppFn> Drop for TestApp<State, AppView, AppFn>
+where
+    AppView: View<State>,
+    AppFn: FnMut(&mut State) -> AppView,
+{
+    fn drop(&mut self) {
+        if !self.owns_root {
+            return;
+        }
+        let mut root = self.root();
+        if let Some(mut parent) = root.get_parent() {
+            parent.remove_child(&root);
+        }
+        root.queue_free();
+    }
+}
+
+// MARK: Snapshots
+
+/// Serializes the subtrees of `nodes` into a stable text format: one line per node with its
+/// class and name (unless Godot generated it), followed by the properties that were set through
+/// attrs or theme overrides according to `dump`, with their current values.
+pub fn snapshot(nodes: &[Gd<Node>], dump: &ViewTreeDump) -> String {
+    fn collect_props(views: &[ViewDump], props: &mut HashMap<i64, Vec<String>>) {
+        for view in views {
+            if let Some(node) = view.nodes.first() {
+                let entry = props.entry(node.instance_id).or_default();
+                for (name, _) in &view.attrs {
+                    if !entry.contains(name) {
+                        entry.push(name.clone());
+                    }
+                }
+            }
+            collect_props(&view.children, props);
+        }
+    }
+    fn fmt_value(value: &Variant) -> String {
+        match value.get_type() {
+            VariantType::STRING | VariantType::STRING_NAME | VariantType::NODE_PATH => {
+                format!("{:?}", value.to_string())
+            }
+            _ => value.to_string(),
+        }
+    }
+    fn write_node(
+        node: &Gd<Node>,
+        props: &HashMap<i64, Vec<String>>,
+        out: &mut String,
+        depth: usize,
+    ) {
+        let indent = "  ".repeat(depth);
+        let name = node.get_name().to_string();
+        write!(out, "{indent}{}", node.get_class()).unwrap();
+        if !name.starts_with('@') {
+            write!(out, " {name:?}").unwrap();
+        }
+        out.push('\n');
+        if let Some(names) = props.get(&node.instance_id().to_i64()) {
+            let mut names = names.clone();
+            names.sort();
+            for name in names {
+                let value = node.get(name.as_str());
+                writeln!(out, "{indent}  .{name} = {}", fmt_value(&value)).unwrap();
+            }
+        }
+        for child in node.get_children().iter_shared() {
+            write_node(&child, props, out, depth + 1);
+        }
+    }
+
+    let mut props = HashMap::new();
+    collect_props(&dump.roots, &mut props);
+    let mut out = String::new();
+    for node in nodes {
+        write_node(node, &props, &mut out, 0);
+    }
+    out
+}
+
+/// Compares `actual` against the snapshot file at `path`. The file gets (re)written instead
+/// if it doesn't exist yet or `GDX_UPDATE_SNAPSHOTS` is set.
+pub fn assert_snapshot(actual: &str, path: impl AsRef<Path>) {
+    let update = std::env::var_os("GDX_UPDATE_SNAPSHOTS").is_some_and(|v| v != "0");
+    check_snapshot(actual, path.as_ref(), update);
+}
+
+fn check_snapshot(actual: &str, path: &Path, update: bool) {
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create snapshot directory");
+        }
+        std::fs::write(path, actual).expect("Failed to write snapshot");
+        return;
+    }
+    let expected = std::fs::read_to_string(path).expect("Failed to read snapshot");
+    if expected != actual {
+        panic!(
+            "Snapshot {} doesn't match (set GDX_UPDATE_SNAPSHOTS=1 to update it):\n{}",
+            path.display(),
+            line_diff(&expected, actual)
+        );
+    }
+}
+
+/// A plain LCS line diff, `-` lines are only in `expected`, `+` lines only in `actual`.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let a = expected.lines().collect::<Vec<_>>();
+    let b = actual.lines().collect::<Vec<_>>();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            writeln!(out, "  {}", a[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            writeln!(out, "+ {}", b[j]).unwrap();
+            j += 1;
+        } else {
+            writeln!(out, "- {}", a[i]).unwrap();
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_of_equal_text_has_no_changes() {
+        assert_eq!(line_diff("a\nb", "a\nb"), "  a\n  b\n");
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        assert_eq!(line_diff("a\nb\nc", "a\nx\nc"), "  a\n+ x\n- b\n  c\n");
+    }
+
+    #[test]
+    fn diff_marks_added_and_removed_lines() {
+        assert_eq!(line_diff("a\nb", "b\nc"), "- a\n  b\n+ c\n");
+        assert_eq!(line_diff("", "a"), "+ a\n");
+        assert_eq!(line_diff("a", ""), "- a\n");
+    }
+
+    #[test]
+    fn empty_snapshot() {
+        assert_eq!(snapshot(&[], &ViewTreeDump { roots: vec![] }), "");
+    }
+
+    #[test]
+    fn snapshot_file_is_written_then_compared() {
+        let dir = std::env::temp_dir().join(format!("gdx_snapshot_{}", std::process::id()));
+        let path = dir.join("nested").join("view.snap");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        check_snapshot("Node \"a\"\n", &path, false);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Node \"a\"\n");
+        check_snapshot("Node \"a\"\n", &path, false);
+
+        let mismatch = std::panic::catch_unwind(|| check_snapshot("Node \"b\"\n", &path, false));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let message = mismatch.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.ends_with("+ Node \"b\"\n- Node \"a\"\n"));
+    }
+}
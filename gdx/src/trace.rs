@@ -0,0 +1,17 @@
+// these expand to nothing unless the `tracing` feature is enabled
+
+macro_rules! trace_span {
+    ($span:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::$span!($($arg)*).entered();
+    };
+}
+pub(crate) use trace_span;
+
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)*);
+    };
+}
+pub(crate) use trace_event;
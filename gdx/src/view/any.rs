@@ -5,7 +5,10 @@ use godot::{
     obj::{Gd, NewAlloc},
 };
 
-use crate::{AnchorType, Context, Message, MessageResult, View, ViewID, view::ArgTuple};
+use crate::{
+    AnchorType, Context, Message, MessageResult, View, ViewID, inspect::Inspector,
+    trace::trace_span, view::ArgTuple,
+};
 
 pub trait AnyView<State: ArgTuple> {
     fn as_any(&self) -> &dyn Any;
@@ -41,6 +44,7 @@ pub trait AnyView<State: ArgTuple> {
         app_state: &mut State,
     ) -> MessageResult;
     fn collect_nodes(&self, state: &AnyViewState, nodes: &mut Vec<Gd<Node>>);
+    fn dyn_inspect(&self, state: &AnyViewState, out: &mut Inspector);
 }
 
 pub struct AnyViewState {
@@ -67,8 +71,10 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) -> AnyViewState {
+        trace_span!(trace_span, "build", view = "Any");
         let mut any_anchor = Node::new_alloc();
         anchor_type.add(anchor, &any_anchor);
+        ctx.stats.nodes_created += 1;
         let inner_id = ctx.new_structural_id();
 
         let inner = ctx.with_id(inner_id, |ctx| {
@@ -86,10 +92,11 @@ where
         prev: &dyn AnyView<State>,
         state: &mut AnyViewState,
         ctx: &mut Context,
-        anchor: &mut Node,
-        anchor_type: AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "rebuild", view = "Any");
         let mut any_anchor = state.anchor.clone();
         if let Some(prev) = prev.as_any().downcast_ref::<V>() {
             let inner = state
@@ -123,10 +130,11 @@ where
         &self,
         state: &mut AnyViewState,
         ctx: &mut Context,
-        anchor: &mut Node,
-        anchor_type: AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "teardown", view = "Any");
         let inner = state
             .inner
             .downcast_mut::<V::ViewState>()
@@ -166,6 +174,16 @@ where
             .expect("What the hell bro");
         self.collect_nodes(inner, nodes);
     }
+
+    fn dyn_inspect(&self, state: &AnyViewState, out: &mut Inspector) {
+        let inner = state
+            .inner
+            .downcast_ref::<V::ViewState>()
+            .expect("What the hell bro");
+        out.view("Any", std::slice::from_ref(&state.anchor), |out| {
+            out.with_id(state.id, |out| self.inspect(inner, out));
+        });
+    }
 }
 
 // MARK: View for dyn AnyView
@@ -221,6 +239,10 @@ macro_rules! dyn_anyview_impl {
             fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
                 self.collect_nodes(state, nodes);
             }
+
+            fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+                self.dyn_inspect(state, out);
+            }
         }
     };
 }
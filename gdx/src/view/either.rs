@@ -4,7 +4,10 @@ use godot::{
     obj::{Gd, NewAlloc},
 };
 
-use crate::{AnchorType, Context, Message, MessageResult, View, ViewID, view::ArgTuple};
+use crate::{
+    AnchorType, Context, Message, MessageResult, View, ViewID, inspect::Inspector,
+    trace::trace_span, view::ArgTuple,
+};
 
 pub struct EitherViewState<AViewState, BViewState> {
     anchor: Gd<Node>,
@@ -26,8 +29,10 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "Either");
         let mut eit_anchor = Node::new_alloc();
         anchor_type.add(anchor, &eit_anchor);
+        ctx.stats.nodes_created += 1;
         let inner_id = ctx.new_structural_id();
         EitherViewState {
             inner: self.as_ref().map_either_with(
@@ -53,10 +58,11 @@ where
         prev: &Self,
         state: &mut Self::ViewState,
         ctx: &mut Context,
-        anchor: &mut Node,
-        anchor_type: AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "rebuild", view = "Either");
         assert_eq!(
             prev.is_left(),
             state.inner.is_left(),
@@ -118,6 +124,7 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "teardown", view = "Either");
         assert_eq!(
             self.is_left(),
             state.inner.is_left(),
@@ -141,6 +148,7 @@ where
 
         anchor_type.remove(anchor, &eit_anchor);
         eit_anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
     }
 
     fn message(
@@ -195,4 +203,14 @@ where
         }
         nodes.push(state.anchor.clone());
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("Either", std::slice::from_ref(&state.anchor), |out| {
+            out.with_id(state.id, |out| match (self, &state.inner) {
+                (Left(val), Left(inner)) => val.inspect(inner, out),
+                (Right(val), Right(inner)) => val.inspect(inner, out),
+                _ => unreachable!(),
+            });
+        });
+    }
 }
@@ -0,0 +1,168 @@
+use godot::{
+    builtin::Variant,
+    classes::{Node, Tween},
+    meta::ToGodot,
+    obj::Inherits,
+    prelude::Gd,
+};
+use std::marker::PhantomData;
+
+use crate::{
+    ElementView, TweenSpec, ViewID,
+    ctx::{Message, MessageResult},
+    inspect::Inspector,
+    view::{AnchorType, ArgTuple, View, element::impl_element_view},
+};
+
+pub struct AnimatedAttr<N, Name, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) name: Name,
+    pub(crate) spec: TweenSpec,
+    pub(crate) value: Variant,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct AnimatedAttrViewState<InnerViewState> {
+    prev_value: Variant,
+    tween: Option<Gd<Tween>>,
+    inner_view_state: InnerViewState,
+}
+
+fn kill(tween: &mut Option<Gd<Tween>>) {
+    if let Some(mut tween) = tween.take()
+        && tween.is_valid()
+    {
+        tween.kill();
+    }
+}
+
+impl<State: ArgTuple, N, Name, Inner> View<State> for AnimatedAttr<N, Name, Inner>
+where
+    Inner: ElementView<N, State>,
+    Name: AsRef<str> + Clone,
+    N: Inherits<Node>,
+{
+    type ViewState = AnimatedAttrViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::ctx::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+        let prev_value = node.upcast_ref().get(self.name.as_ref());
+        // nothing to animate from yet
+        node.upcast_mut().set(self.name.as_ref(), &self.value);
+        ctx.stats.attrs_written += 1;
+        AnimatedAttrViewState {
+            prev_value,
+            tween: None,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::ctx::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+
+        let mut node = self.get_node(state).upcast::<Node>();
+        if self.name.as_ref() != prev.name.as_ref() {
+            kill(&mut state.tween);
+            node.set(prev.name.as_ref(), &state.prev_value);
+            state.prev_value = node.get(self.name.as_ref());
+            node.set(self.name.as_ref(), &self.value);
+            ctx.stats.attrs_written += 1;
+        } else if self.value != prev.value {
+            // retargeting, the new tween starts wherever the old one left the property
+            kill(&mut state.tween);
+            if let Some(mut tween) = node.create_tween() {
+                self.spec.tween_property(
+                    &mut tween,
+                    &mut node,
+                    self.name.as_ref(),
+                    None,
+                    self.value.clone(),
+                );
+                state.tween = Some(tween);
+            } else {
+                node.set(self.name.as_ref(), &self.value);
+            }
+            ctx.stats.attrs_written += 1;
+        }
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::ctx::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        kill(&mut state.tween);
+        if ctx.pooling() {
+            let mut node = self.get_node(state);
+            node.upcast_mut().set(self.name.as_ref(), &state.prev_value);
+            ctx.stats.attrs_written += 1;
+        }
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        self.inner
+            .message(msg, path, &mut view_state.inner_view_state, app_state)
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr(self.name.as_ref(), self.value.to_string());
+    }
+}
+
+impl<State: ArgTuple, N, Name, Inner> ElementView<N, State> for AnimatedAttr<N, Name, Inner>
+where
+    Inner: ElementView<N, State>,
+    Name: AsRef<str> + Clone,
+    N: Inherits<Node>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Name0, Inner> AnimatedAttr<N, Name0, Inner> {
+    impl_element_view! { N }
+}
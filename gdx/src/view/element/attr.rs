@@ -4,6 +4,7 @@ use std::marker::PhantomData;
 use crate::{
     ElementView, ViewID,
     ctx::{Message, MessageResult},
+    inspect::Inspector,
     view::{AnchorType, ArgTuple, View, element::impl_element_view},
 };
 
@@ -39,6 +40,7 @@ where
         let mut node = self.inner.get_node(&inner_view_state);
         let prev_value = node.upcast_ref().get(self.name.as_ref());
         node.upcast_mut().set(self.name.as_ref(), &self.value);
+        ctx.stats.attrs_written += 1;
         AttrViewState {
             prev_value,
             inner_view_state,
@@ -67,9 +69,11 @@ where
             let mut node = self.get_node(state);
             if self.name.as_ref() != prev.name.as_ref() {
                 node.upcast_mut().set(prev.name.as_ref(), &state.prev_value);
+                ctx.stats.attrs_written += 1;
             }
             state.prev_value = node.upcast_ref().get(self.name.as_ref());
             node.upcast_mut().set(self.name.as_ref(), &self.value);
+            ctx.stats.attrs_written += 1;
         }
     }
 
@@ -81,6 +85,11 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        if ctx.pooling() {
+            let mut node = self.get_node(state);
+            node.upcast_mut().set(self.name.as_ref(), &state.prev_value);
+            ctx.stats.attrs_written += 1;
+        }
         self.inner.teardown(
             &mut state.inner_view_state,
             ctx,
@@ -104,6 +113,11 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.inner.collect_nodes(&state.inner_view_state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr(self.name.as_ref(), self.value.to_string());
+    }
 }
 
 impl<State: ArgTuple, N, Name, Inner, const BUILD_ONLY: bool> ElementView<N, State>
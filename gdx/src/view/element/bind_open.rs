@@ -0,0 +1,204 @@
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{Node, Window},
+    meta::ToGodot,
+    obj::Inherits,
+    prelude::Gd,
+};
+use std::{cell::Cell, marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    AnchorType, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage,
+    inspect::Inspector,
+    view::{ArgTuple, element::impl_element_view},
+};
+
+/// Shows the window while the `bool` that `lens` points at is true, and sets it back to false
+/// when the user closes the window.
+pub struct BindOpen<N, Lens, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) lens: Lens,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct BindOpenViewState<InnerViewState> {
+    id: ViewID,
+    // what the state last asked for, hiding the window because of it isn't the user closing it
+    wanted: Rc<Cell<bool>>,
+    closed: Callable,
+    close_requested: Callable,
+    inner_view_state: InnerViewState,
+}
+
+fn set_open(window: &mut Gd<Window>, wanted: &Rc<Cell<bool>>, open: bool) {
+    wanted.set(open);
+    match (open, window.is_visible()) {
+        // popping up has to wait until the window is in the tree, the state might have changed by then
+        (true, false) if !window.is_inside_tree() => {
+            let (mut window, wanted) = (window.clone(), wanted.clone());
+            Callable::from_fn("popup_centered", move |_| {
+                if wanted.get() && !window.is_visible() {
+                    window.popup_centered();
+                }
+            })
+            .call_deferred(&[]);
+        }
+        (true, false) => window.popup_centered(),
+        (false, true) => window.hide(),
+        _ => {}
+    }
+}
+
+impl<N, State: ArgTuple, Lens, Inner> View<State> for BindOpen<N, Lens, Inner>
+where
+    Inner: ElementView<N, State>,
+    Lens: Fn(&mut State) -> &mut bool,
+    N: Inherits<Node> + Inherits<Window>,
+{
+    type ViewState = BindOpenViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut window = self.inner.get_node(&inner_view_state).upcast::<Window>();
+        // a plain `Window` starts out visible, which would skip centering it when it's open right away
+        window.hide();
+
+        let id = ctx.new_structural_id();
+        let wanted = Rc::new(Cell::new(false));
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        // dialogs hide themselves on cancel and confirm, this catches all of them
+        let (hidden, open) = (window.clone(), wanted.clone());
+        let closed = Callable::from_fn("closed", move |_| {
+            if !hidden.is_visible() && open.get() {
+                msgs.lock().push_back(FullMessage {
+                    msg: Message::Signal {
+                        name: "closed".into(),
+                        args: Box::new([]),
+                    },
+                    path: path.clone(),
+                });
+            }
+        });
+        // a plain `Window` ignores its close button unless told otherwise
+        let mut requested = window.clone();
+        let close_requested = Callable::from_fn("close_requested", move |_| {
+            requested.hide();
+        });
+        window.connect("visibility_changed", &closed);
+        window.connect("close_requested", &close_requested);
+        ctx.stats.signals_connected += 2;
+
+        set_open(&mut window, &wanted, *(self.lens)(app_state));
+
+        BindOpenViewState {
+            id,
+            wanted,
+            closed,
+            close_requested,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        let mut window = self.get_node(state).upcast::<Window>();
+        set_open(&mut window, &state.wanted, *(self.lens)(app_state));
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        // the node outlives the view, so it must not send messages anymore
+        if ctx.keep_nodes || ctx.pooling() {
+            let mut window = self.get_node(state).upcast::<Window>();
+            window.disconnect("visibility_changed", &state.closed);
+            window.disconnect("close_requested", &state.close_requested);
+            ctx.stats.signals_disconnected += 2;
+            if ctx.pooling() {
+                window.hide();
+            }
+        }
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { .. }, Some((start, []))) if *start == view_state.id => {
+                let open = (self.lens)(app_state);
+                if !*open {
+                    return MessageResult::Untracked;
+                }
+                *open = false;
+                MessageResult::Success
+            }
+            (msg, _) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal("visibility_changed");
+        out.signal("close_requested");
+    }
+}
+
+impl<N, State: ArgTuple, Lens, Inner> ElementView<N, State> for BindOpen<N, Lens, Inner>
+where
+    Inner: ElementView<N, State>,
+    Lens: Fn(&mut State) -> &mut bool,
+    N: Inherits<Node> + Inherits<Window>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Lens0, Inner> BindOpen<N, Lens0, Inner> {
+    impl_element_view! { N }
+}
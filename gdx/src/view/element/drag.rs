@@ -0,0 +1,413 @@
+use godot::{
+    builtin::{Variant, vdict},
+    classes::{Control, Node, node::DuplicateFlags},
+    meta::ToGodot,
+    obj::{Inherits, NewAlloc},
+    prelude::Gd,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    AnchorType, ElementView, Message, MessageResult, View, ViewID,
+    classes::VirtualHost,
+    ctx::FullMessage,
+    inspect::{Inspector, short_type_name},
+    view::{ArgTuple, element::impl_element_view},
+};
+
+const TOKEN_KEY: &str = "gdx_drag";
+
+struct DragPayload {
+    token: i64,
+    value: Box<dyn Any>,
+}
+
+thread_local! {
+    // godot only carries a `Variant` through the drag, the rust value waits here
+    static PAYLOAD: RefCell<Option<DragPayload>> = const { RefCell::new(None) };
+    static NEXT_TOKEN: Cell<i64> = const { Cell::new(0) };
+}
+
+fn start_drag(value: Box<dyn Any>) -> Variant {
+    let token = NEXT_TOKEN.with(|next| {
+        let token = next.get();
+        next.set(token + 1);
+        token
+    });
+    PAYLOAD.with_borrow_mut(|payload| *payload = Some(DragPayload { token, value }));
+    vdict! { TOKEN_KEY: token }.to_variant()
+}
+
+fn drag_token(data: &Variant) -> Option<i64> {
+    data.try_to::<godot::builtin::VarDictionary>()
+        .ok()?
+        .get(TOKEN_KEY)?
+        .try_to()
+        .ok()
+}
+
+fn payload_is<T: 'static>(token: i64) -> bool {
+    PAYLOAD.with_borrow(|payload| {
+        payload
+            .as_ref()
+            .is_some_and(|payload| payload.token == token && payload.value.is::<T>())
+    })
+}
+
+fn take_payload<T: 'static>(token: i64) -> Option<T> {
+    PAYLOAD.with_borrow_mut(|payload| {
+        if payload.as_ref().is_none_or(|p| p.token != token) {
+            return None;
+        }
+        payload.take()?.value.downcast().ok().map(|value| *value)
+    })
+}
+
+/// Makes the control draggable, carrying a clone of `payload` to a [`DropTarget`] for the same type.
+/// The payload stays a rust value, only a token for it goes through godot.
+pub struct Draggable<N, T, Preview, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) payload: T,
+    pub(crate) preview: Preview,
+    pub(crate) _p: PhantomData<N>,
+}
+
+impl<N, T, Preview, Inner> Draggable<N, T, Preview, Inner> {
+    /// A view shown under the cursor while dragging, it's kept up to date with the state
+    /// and copied when a drag starts.
+    pub fn preview<P>(self, preview: P) -> Draggable<N, T, P, Inner> {
+        Draggable {
+            inner: self.inner,
+            payload: self.payload,
+            preview,
+            _p: PhantomData,
+        }
+    }
+}
+
+pub struct DraggableViewState<T, PreviewViewState, InnerViewState> {
+    id: ViewID,
+    payload: Rc<RefCell<T>>,
+    holder: Gd<Control>,
+    preview_view_state: PreviewViewState,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, T, Preview, Inner> View<State> for Draggable<N, T, Preview, Inner>
+where
+    Inner: ElementView<N, State>,
+    T: Clone + 'static,
+    Preview: View<State>,
+    N: VirtualHost + Inherits<Control>,
+{
+    type ViewState = DraggableViewState<T, Preview::ViewState, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+
+        // the preview lives outside the tree, each drag gets a copy since godot frees it afterwards
+        let holder = Control::new_alloc();
+        ctx.stats.nodes_created += 1;
+        let id = ctx.new_structural_id();
+        let preview_view_state = ctx.with_id(id, |ctx| {
+            self.preview.build(
+                ctx,
+                &mut holder.clone().upcast::<Node>(),
+                AnchorType::ChildOf,
+                app_state,
+            )
+        });
+
+        let payload = Rc::new(RefCell::new(self.payload.clone()));
+        let drag_payload = payload.clone();
+        let drag_holder = holder.clone();
+        let control = node.clone().upcast::<Control>();
+        N::with_overrides(&mut node, |overrides| {
+            overrides.get_drag_data = Some(Rc::new(move |_| {
+                if drag_holder.get_child_count() > 0
+                    && let Some(preview) = drag_holder
+                        .duplicate_ex()
+                        .flags(DuplicateFlags::GROUPS | DuplicateFlags::SCRIPTS)
+                        .done()
+                        .and_then(|preview| preview.try_cast::<Control>().ok())
+                {
+                    control.clone().set_drag_preview(&preview);
+                }
+                start_drag(Box::new(drag_payload.borrow().clone()))
+            }));
+        });
+
+        DraggableViewState {
+            id,
+            payload,
+            holder,
+            preview_view_state,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        *state.payload.borrow_mut() = self.payload.clone();
+        ctx.with_id(state.id, |ctx| {
+            self.preview.rebuild(
+                &prev.preview,
+                &mut state.preview_view_state,
+                ctx,
+                &mut state.holder.clone().upcast::<Node>(),
+                AnchorType::ChildOf,
+                app_state,
+            );
+        });
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state);
+        N::with_overrides(&mut node, |overrides| overrides.get_drag_data = None);
+        ctx.with_id(state.id, |ctx| {
+            self.preview.teardown(
+                &mut state.preview_view_state,
+                ctx,
+                &mut state.holder.clone().upcast::<Node>(),
+                AnchorType::ChildOf,
+                app_state,
+            );
+        });
+        state.holder.queue_free();
+        ctx.stats.nodes_freed += 1;
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match path.split_first() {
+            Some((start, rest)) if *start == view_state.id => {
+                self.preview
+                    .message(msg, rest, &mut view_state.preview_view_state, app_state)
+            }
+            _ => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr("draggable", short_type_name::<T>());
+    }
+}
+
+impl<N, State: ArgTuple, T, Preview, Inner> ElementView<N, State>
+    for Draggable<N, T, Preview, Inner>
+where
+    Inner: ElementView<N, State>,
+    T: Clone + 'static,
+    Preview: View<State>,
+    N: VirtualHost + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, T0, Preview0, Inner> Draggable<N, T0, Preview0, Inner> {
+    impl_element_view! { N }
+}
+
+/// Accepts drops of a [`Draggable`] payload of type `T`, `cb` gets the payload itself.
+pub struct DropTarget<N, T, Cb, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) cb: Cb,
+    pub(crate) _p: PhantomData<(N, T)>,
+}
+
+pub struct DropTargetViewState<InnerViewState> {
+    id: ViewID,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, T, Cb, Inner> View<State> for DropTarget<N, T, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    T: 'static,
+    Cb: Fn(&mut State, T),
+    N: VirtualHost + Inherits<Control>,
+{
+    type ViewState = DropTargetViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+
+        let id = ctx.new_structural_id();
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        N::with_overrides(&mut node, |overrides| {
+            overrides.can_drop_data = Some(Rc::new(|_, data| {
+                drag_token(data).is_some_and(payload_is::<T>)
+            }));
+            overrides.drop_data = Some(Rc::new(move |_, data| {
+                if let Some(token) = drag_token(&data) {
+                    msgs.lock().push_back(FullMessage {
+                        msg: Message::Signal {
+                            name: "drop_data".into(),
+                            args: Box::new([token.to_variant()]),
+                        },
+                        path: path.clone(),
+                    });
+                }
+            }));
+        });
+
+        DropTargetViewState {
+            id,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state);
+        N::with_overrides(&mut node, |overrides| {
+            overrides.can_drop_data = None;
+            overrides.drop_data = None;
+        });
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { args, .. }, Some((start, []))) if *start == view_state.id => {
+                if let Some(payload) = args
+                    .first()
+                    .and_then(|token| token.try_to().ok())
+                    .and_then(take_payload::<T>)
+                {
+                    (self.cb)(app_state, payload);
+                }
+                MessageResult::Success
+            }
+            (msg, _) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr("drop_target", short_type_name::<T>());
+    }
+}
+
+impl<N, State: ArgTuple, T, Cb, Inner> ElementView<N, State> for DropTarget<N, T, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    T: 'static,
+    Cb: Fn(&mut State, T),
+    N: VirtualHost + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, T0, Cb0, Inner> DropTarget<N, T0, Cb0, Inner> {
+    impl_element_view! { N }
+}
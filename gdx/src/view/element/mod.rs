@@ -1,23 +1,31 @@
+pub mod animated_attr;
 pub mod attr;
+pub mod bind_open;
+pub mod drag;
 pub mod on_build;
+pub mod on_input;
 pub mod on_mounted;
+pub mod on_process;
 pub mod on_rebuild;
 pub mod on_signal;
 pub mod on_teardown;
 pub mod theme_override;
+pub mod transition;
+pub mod virtuals;
 
 use std::marker::PhantomData;
 
 use godot::{
     builtin::Variant,
     classes::Node,
-    global::godot_print,
     meta::ToGodot,
     obj::{Gd, Inherits, NewAlloc},
 };
 
 use crate::{
     ctx::{Message, MessageResult},
+    inspect::{Inspector, short_type_name},
+    trace::trace_span,
     view::{AnchorType, ArgTuple, View, ViewID},
 };
 
@@ -61,7 +69,17 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) -> Self::ViewState {
-        let mut node = N::new_alloc();
+        trace_span!(trace_span, "build", view = %short_type_name::<N>());
+        let mut node = match ctx.node_pool.as_mut().and_then(|pool| pool.take::<N>()) {
+            Some(node) => {
+                ctx.stats.nodes_reused += 1;
+                node
+            }
+            None => {
+                ctx.stats.nodes_created += 1;
+                N::new_alloc()
+            }
+        };
         anchor_type.add(anchor, &node.clone().upcast::<Node>());
 
         let child_id = ctx.new_structural_id();
@@ -86,10 +104,11 @@ where
         prev: &Self,
         state: &mut Self::ViewState,
         ctx: &mut crate::ctx::Context,
-        anchor: &mut Node,
-        anchor_type: AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "rebuild", view = %short_type_name::<N>());
         ctx.with_id(state.child_id, |ctx| {
             self.children.rebuild(
                 &prev.children,
@@ -110,6 +129,7 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "teardown", view = %short_type_name::<N>());
         ctx.with_id(state.child_id, |ctx| {
             self.children.teardown(
                 &mut state.child_view_state,
@@ -120,8 +140,20 @@ where
             );
         });
 
+        if ctx.keep_nodes {
+            return;
+        }
         anchor_type.remove(anchor, &state.node.clone().upcast());
-        state.node.upcast_mut::<Node>().queue_free();
+        let parked = ctx
+            .node_pool
+            .as_mut()
+            .is_some_and(|pool| pool.park(state.node.clone()).is_ok());
+        if parked {
+            ctx.stats.nodes_pooled += 1;
+        } else {
+            state.node.upcast_mut::<Node>().queue_free();
+            ctx.stats.nodes_freed += 1;
+        }
     }
 
     fn message(
@@ -146,6 +178,18 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         nodes.push(state.node.clone().upcast::<Node>());
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view(
+            format!("Element<{}>", short_type_name::<N>()),
+            &[state.node.clone().upcast()],
+            |out| {
+                out.with_id(state.child_id, |out| {
+                    self.children.inspect(&state.child_view_state, out);
+                });
+            },
+        );
+    }
 }
 
 pub trait ElementView<N: Inherits<Node>, State: ArgTuple>: View<State> + Sized {
@@ -183,6 +227,27 @@ macro_rules! impl_element_view {
                 _p: PhantomData,
             }
         }
+        /// Like [`attr`](Self::attr), but changes of the value between rebuilds are tweened.
+        pub fn animated_attr<Name, Value>(
+            self,
+            name: Name,
+            spec: $crate::TweenSpec,
+            value: Value,
+        ) -> $crate::AnimatedAttr<$node, Name, Self>
+        where
+            Name: AsRef<str>,
+            Value: ToGodot,
+            $node: godot::prelude::Inherits<godot::prelude::Node>,
+        {
+            use std::marker::PhantomData;
+            $crate::AnimatedAttr {
+                inner: self,
+                name,
+                spec,
+                value: value.to_variant(),
+                _p: PhantomData,
+            }
+        }
         pub fn on_signal<State, Name, Cb>(
             self,
             name: Name,
@@ -201,6 +266,99 @@ macro_rules! impl_element_view {
                 _p: PhantomData,
             }
         }
+        /// Left mouse button presses.
+        pub fn on_click<State, Cb>(
+            self,
+            cb: Cb,
+        ) -> $crate::OnInput<$node, godot::classes::InputEventMouseButton, Cb, Self>
+        where
+            Cb: Fn(&mut State, Gd<godot::classes::InputEventMouseButton>),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnInput {
+                inner: self,
+                filter: $crate::view::element::on_input::is_click,
+                accept: false,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        /// Mouse wheel presses, in any direction.
+        pub fn on_scroll<State, Cb>(
+            self,
+            cb: Cb,
+        ) -> $crate::OnInput<$node, godot::classes::InputEventMouseButton, Cb, Self>
+        where
+            Cb: Fn(&mut State, Gd<godot::classes::InputEventMouseButton>),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnInput {
+                inner: self,
+                filter: crate::view::element::on_input::is_scroll,
+                accept: false,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        /// Key presses, without echoes.
+        pub fn on_key_pressed<State, Cb>(
+            self,
+            cb: Cb,
+        ) -> $crate::OnInput<$node, godot::classes::InputEventKey, Cb, Self>
+        where
+            Cb: Fn(&mut State, Gd<godot::classes::InputEventKey>),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnInput {
+                inner: self,
+                filter: crate::view::element::on_input::is_key_press,
+                accept: false,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        pub fn on_input<E, State, Cb>(self, cb: Cb) -> $crate::OnInput<$node, E, Cb, Self>
+        where
+            E: godot::obj::GodotClass + godot::prelude::Inherits<godot::classes::InputEvent>,
+            Cb: Fn(&mut State, Gd<E>),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnInput {
+                inner: self,
+                filter: crate::view::element::on_input::any_event::<E>,
+                accept: false,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        pub fn on_mouse_enter<State, Cb>(self, cb: Cb) -> $crate::OnHover<$node, Cb, Self, true>
+        where
+            Cb: Fn(&mut State),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnHover {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        pub fn on_mouse_exit<State, Cb>(self, cb: Cb) -> $crate::OnHover<$node, Cb, Self, false>
+        where
+            Cb: Fn(&mut State),
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnHover {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
         pub fn on_mounted<State, Cb>(self, cb: Cb) -> $crate::OnMounted<$node, Cb, Self>
         where
             Cb: Fn(&mut State, Gd<$node>),
@@ -225,6 +383,33 @@ macro_rules! impl_element_view {
                 _p: PhantomData,
             }
         }
+        pub fn on_process<State, Cb>(self, cb: Cb) -> $crate::OnProcess<$node, Cb, Self, false>
+        where
+            Cb: Fn(&mut State, f64),
+            $node: godot::prelude::Inherits<godot::prelude::Node>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnProcess {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        pub fn on_physics_process<State, Cb>(
+            self,
+            cb: Cb,
+        ) -> $crate::OnProcess<$node, Cb, Self, true>
+        where
+            Cb: Fn(&mut State, f64),
+            $node: godot::prelude::Inherits<godot::prelude::Node>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnProcess {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
         pub fn on_rebuild<Cb>(self, cb: Cb) -> $crate::OnRebuild<$node, Cb, Self>
         where
             Cb: Fn(Gd<$node>),
@@ -266,6 +451,219 @@ macro_rules! impl_element_view {
                 _p: PhantomData,
             }
         }
+        pub fn get_drag_data(
+            self,
+            f: impl Fn(godot::builtin::Vector2) -> Variant + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::GetDragData(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn can_drop_data(
+            self,
+            f: impl Fn(godot::builtin::Vector2, &Variant) -> bool + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::CanDropData(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn make_custom_tooltip(
+            self,
+            f: impl Fn(godot::builtin::GString) -> Option<Gd<godot::classes::Control>> + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::MakeCustomTooltip(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn has_point(
+            self,
+            f: impl Fn(godot::builtin::Vector2) -> bool + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::HasPoint(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn get_minimum_size(
+            self,
+            f: impl Fn() -> godot::builtin::Vector2 + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::GetMinimumSize(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn draw(
+            self,
+            f: impl Fn(Gd<godot::classes::CanvasItem>) + 'static,
+        ) -> $crate::Virtual<$node, Self>
+        where
+            $node: $crate::VirtualHost,
+        {
+            use std::marker::PhantomData;
+            $crate::Virtual {
+                inner: self,
+                f: $crate::VirtualFn::Draw(std::rc::Rc::new(f)),
+                _p: PhantomData,
+            }
+        }
+        pub fn draggable<Payload>(
+            self,
+            payload: Payload,
+        ) -> $crate::Draggable<$node, Payload, (), Self>
+        where
+            Payload: Clone + 'static,
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::Draggable {
+                inner: self,
+                payload,
+                preview: (),
+                _p: PhantomData,
+            }
+        }
+        pub fn drop_target<Payload, State, Cb>(
+            self,
+            cb: Cb,
+        ) -> $crate::DropTarget<$node, Payload, Cb, Self>
+        where
+            Payload: 'static,
+            Cb: Fn(&mut State, Payload),
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::DropTarget {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        /// Pops the window up while `lens` points at `true`, closing it sets that back to `false`.
+        pub fn bind_open<State, Lens>(self, lens: Lens) -> $crate::BindOpen<$node, Lens, Self>
+        where
+            Lens: Fn(&mut State) -> &mut bool,
+            $node: godot::prelude::Inherits<godot::classes::Window>,
+        {
+            use std::marker::PhantomData;
+            $crate::BindOpen {
+                inner: self,
+                lens,
+                _p: PhantomData,
+            }
+        }
+        /// Declares the items of a `Tree`, built from [`tree_item`]($crate::tree_item)s.
+        pub fn tree_items<Items>(self, children: Items) -> $crate::TreeItems<$node, Items, Self>
+        where
+            $node: godot::prelude::Inherits<godot::classes::Tree>,
+        {
+            use std::marker::PhantomData;
+            $crate::TreeItems {
+                inner: self,
+                children,
+                _p: PhantomData,
+            }
+        }
+        /// Declares the items of an [`ItemHost`]($crate::ItemHost), built from [`list_item`]($crate::list_item)s.
+        pub fn list_items<Items>(self, children: Items) -> $crate::ListItems<$node, Items, Self>
+        where
+            $node: $crate::ItemHost,
+        {
+            use std::marker::PhantomData;
+            $crate::ListItems {
+                inner: self,
+                children,
+                _p: PhantomData,
+            }
+        }
+        /// Right clicking the element opens a menu of [`list_item`]($crate::list_item)s and [`submenu`]($crate::submenu)s.
+        pub fn context_menu<Items>(self, children: Items) -> $crate::ContextMenu<$node, Items, Self>
+        where
+            $node: godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::ContextMenu {
+                inner: self,
+                children,
+                _p: PhantomData,
+            }
+        }
+        pub fn on_drop_data<State, Cb>(self, cb: Cb) -> $crate::OnDropData<$node, Cb, Self>
+        where
+            Cb: Fn(&mut State, godot::builtin::Vector2, Variant),
+            $node: $crate::VirtualHost + godot::prelude::Inherits<godot::classes::Control>,
+        {
+            use std::marker::PhantomData;
+            $crate::OnDropData {
+                inner: self,
+                cb,
+                _p: PhantomData,
+            }
+        }
+        pub fn transition(
+            self,
+            transition: $crate::Transition,
+        ) -> $crate::WithTransition<$node, Self> {
+            use std::marker::PhantomData;
+            $crate::WithTransition {
+                inner: self,
+                enter: Some(transition.clone()),
+                exit: Some(transition),
+                _p: PhantomData,
+            }
+        }
+        pub fn transition_in(
+            self,
+            transition: $crate::Transition,
+        ) -> $crate::WithTransition<$node, Self> {
+            use std::marker::PhantomData;
+            $crate::WithTransition {
+                inner: self,
+                enter: Some(transition),
+                exit: None,
+                _p: PhantomData,
+            }
+        }
+        pub fn transition_out(
+            self,
+            transition: $crate::Transition,
+        ) -> $crate::WithTransition<$node, Self> {
+            use std::marker::PhantomData;
+            $crate::WithTransition {
+                inner: self,
+                enter: None,
+                exit: Some(transition),
+                _p: PhantomData,
+            }
+        }
     };
 }
 pub(crate) use impl_element_view;
@@ -0,0 +1,357 @@
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{Control, InputEvent, InputEventKey, InputEventMouseButton, Node},
+    global::MouseButton,
+    meta::ToGodot,
+    obj::{GodotClass, Inherits},
+    prelude::Gd,
+};
+use std::{cell::Cell, marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    AnchorType, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage,
+    inspect::Inspector,
+    view::{ArgTuple, element::impl_element_view},
+};
+
+pub type InputFilter<E> = fn(&Gd<E>) -> bool;
+
+pub(crate) fn is_click(event: &Gd<InputEventMouseButton>) -> bool {
+    event.is_pressed() && event.get_button_index() == MouseButton::LEFT
+}
+pub(crate) fn is_scroll(event: &Gd<InputEventMouseButton>) -> bool {
+    event.is_pressed()
+        && matches!(
+            event.get_button_index(),
+            MouseButton::WHEEL_UP
+                | MouseButton::WHEEL_DOWN
+                | MouseButton::WHEEL_LEFT
+                | MouseButton::WHEEL_RIGHT
+        )
+}
+pub(crate) fn is_key_press(event: &Gd<InputEventKey>) -> bool {
+    event.is_pressed() && !event.is_echo()
+}
+pub(crate) fn any_event<E: GodotClass>(_: &Gd<E>) -> bool {
+    true
+}
+
+/// Calls `cb` for the `gui_input` events that cast to `E` and pass the filter.
+/// Filtering (and accepting, if enabled) happens right when the event comes in, so a
+/// handled event doesn't propagate further even though `cb` only runs in the next [`App::run`](crate::App::run).
+pub struct OnInput<N, E: GodotClass, Cb, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) filter: InputFilter<E>,
+    pub(crate) accept: bool,
+    pub(crate) cb: Cb,
+    pub(crate) _p: PhantomData<N>,
+}
+
+impl<N, E: GodotClass, Cb, Inner> OnInput<N, E, Cb, Inner> {
+    /// Calls `accept_event()` on the control for every event this modifier handles.
+    pub fn accept(mut self, accept: bool) -> Self {
+        self.accept = accept;
+        self
+    }
+}
+
+pub struct OnInputViewState<InnerViewState> {
+    id: ViewID,
+    accept: Rc<Cell<bool>>,
+    callable: Callable,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, E, Cb, Inner> View<State> for OnInput<N, E, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    E: GodotClass + Inherits<InputEvent>,
+    Cb: Fn(&mut State, Gd<E>),
+    N: Inherits<Node> + Inherits<Control>,
+{
+    type ViewState = OnInputViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let node = self.inner.get_node(&inner_view_state).upcast::<Control>();
+
+        // several input modifiers can sit on one element, so each gets its own spot in the path
+        let id = ctx.new_structural_id();
+        let accept = Rc::new(Cell::new(self.accept));
+
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        let filter = self.filter;
+        let accept_flag = accept.clone();
+        let mut control = node.clone();
+        let callable = Callable::from_fn("input", move |args| {
+            let Some(event) = args
+                .first()
+                .and_then(|v| v.try_to::<Gd<InputEvent>>().ok())
+                .and_then(|v| v.try_cast::<E>().ok())
+            else {
+                return;
+            };
+            if !filter(&event) {
+                return;
+            }
+            if accept_flag.get() {
+                control.accept_event();
+            }
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "gui_input".into(),
+                    args: Box::new([event.to_variant()]),
+                },
+                path: path.clone(),
+            });
+        });
+
+        node.clone()
+            .upcast::<Node>()
+            .connect("gui_input", &callable);
+        ctx.stats.signals_connected += 1;
+        OnInputViewState {
+            id,
+            accept,
+            callable,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        state.accept.set(self.accept);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state).upcast::<Node>();
+        node.disconnect("gui_input", &state.callable);
+        ctx.stats.signals_disconnected += 1;
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { args, .. }, Some((start, []))) if *start == view_state.id => {
+                if let Some(event) = args.first().and_then(|v| v.try_to::<Gd<E>>().ok()) {
+                    (self.cb)(app_state, event);
+                }
+                MessageResult::Success
+            }
+            (msg, _) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal(format!("gui_input<{}>", E::class_id()));
+    }
+}
+
+impl<N, State: ArgTuple, E, Cb, Inner> ElementView<N, State> for OnInput<N, E, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    E: GodotClass + Inherits<InputEvent>,
+    Cb: Fn(&mut State, Gd<E>),
+    N: Inherits<Node> + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, E0: GodotClass, Cb0, Inner> OnInput<N, E0, Cb0, Inner> {
+    impl_element_view! { N }
+}
+
+/// Calls `cb` when the mouse enters (or exits, if `!ENTER`) the control.
+pub struct OnHover<N, Cb, Inner, const ENTER: bool> {
+    pub(crate) inner: Inner,
+    pub(crate) cb: Cb,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct OnHoverViewState<InnerViewState> {
+    id: ViewID,
+    callable: Callable,
+    inner_view_state: InnerViewState,
+}
+
+const fn hover_signal(enter: bool) -> &'static str {
+    if enter {
+        "mouse_entered"
+    } else {
+        "mouse_exited"
+    }
+}
+
+impl<N, State: ArgTuple, Cb, Inner, const ENTER: bool> View<State> for OnHover<N, Cb, Inner, ENTER>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State),
+    N: Inherits<Node> + Inherits<Control>,
+{
+    type ViewState = OnHoverViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state).upcast::<Node>();
+
+        let id = ctx.new_structural_id();
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        let callable = Callable::from_fn("hover", move |_| {
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: hover_signal(ENTER).into(),
+                    args: Box::new([]),
+                },
+                path: path.clone(),
+            });
+        });
+
+        node.connect(hover_signal(ENTER), &callable);
+        ctx.stats.signals_connected += 1;
+        OnHoverViewState {
+            id,
+            callable,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state).upcast::<Node>();
+        node.disconnect(hover_signal(ENTER), &state.callable);
+        ctx.stats.signals_disconnected += 1;
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { .. }, Some((start, []))) if *start == view_state.id => {
+                (self.cb)(app_state);
+                MessageResult::Success
+            }
+            (msg, _) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal(hover_signal(ENTER));
+    }
+}
+
+impl<N, State: ArgTuple, Cb, Inner, const ENTER: bool> ElementView<N, State>
+    for OnHover<N, Cb, Inner, ENTER>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State),
+    N: Inherits<Node> + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Cb0, Inner, const ENTER0: bool> OnHover<N, Cb0, Inner, ENTER0> {
+    impl_element_view! { N }
+}
@@ -5,6 +5,7 @@ use godot::{builtin::Variant, classes::Node, meta::ToGodot, obj::Inherits, prelu
 use crate::{
     AnchorType, ElementView, Message, MessageResult, View,
     ctx::FullMessage,
+    inspect::Inspector,
     view::{ArgTuple, element::impl_element_view},
 };
 
@@ -103,6 +104,10 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.inner.collect_nodes(&state.inner_view_state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+    }
 }
 
 impl<N, State: ArgTuple, Cb, Inner> ElementView<N, State> for OnMounted<N, Cb, Inner>
@@ -0,0 +1,178 @@
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{Engine, Node, SceneTree},
+    meta::ToGodot,
+    obj::{Inherits, Singleton},
+    prelude::Gd,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::{
+    AnchorType, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage,
+    inspect::Inspector,
+    view::{ArgTuple, element::impl_element_view},
+};
+
+/// Calls `cb` with the frame's delta every (physics) frame while mounted,
+/// unless the node can't process, e.g. because the tree is paused.
+/// Changes made in `cb` don't get recorded as history entries.
+pub struct OnProcess<N, Cb, Inner, const PHYSICS: bool> {
+    pub(crate) inner: Inner,
+    pub(crate) cb: Cb,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct OnProcessViewState<InnerViewState> {
+    connection: Option<(Gd<SceneTree>, Callable)>,
+    inner_view_state: InnerViewState,
+}
+
+const fn signal_name(physics: bool) -> &'static str {
+    if physics {
+        "physics_frame"
+    } else {
+        "process_frame"
+    }
+}
+
+impl<N, State: ArgTuple, Cb, Inner, const PHYSICS: bool> View<State>
+    for OnProcess<N, Cb, Inner, PHYSICS>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State, f64),
+    N: Inherits<Node>,
+{
+    type ViewState = OnProcessViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+
+        // the node itself might not be in the tree yet, so go through the main loop
+        let connection = Engine::singleton()
+            .get_main_loop()
+            .and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok())
+            .map(|mut tree| {
+                let msgs = ctx.msg_queue.clone();
+                let path: Arc<[ViewID]> = ctx.path.clone().into();
+                let callable = Callable::from_fn("frame", move |_| {
+                    msgs.lock().push_back(FullMessage {
+                        msg: Message::Signal {
+                            name: signal_name(PHYSICS).into(),
+                            args: Box::new([]),
+                        },
+                        path: path.clone(),
+                    });
+                });
+                tree.connect(signal_name(PHYSICS), &callable);
+                ctx.stats.signals_connected += 1;
+                (tree, callable)
+            });
+
+        OnProcessViewState {
+            connection,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        if let Some((mut tree, callable)) = state.connection.take() {
+            tree.disconnect(signal_name(PHYSICS), &callable);
+            ctx.stats.signals_disconnected += 1;
+        }
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match msg {
+            Message::Signal { ref name, .. }
+                if path.is_empty() && &**name == signal_name(PHYSICS) =>
+            {
+                let node = self.get_node(view_state).upcast::<Node>();
+                if !node.can_process() {
+                    return MessageResult::Stale(msg);
+                }
+                let delta = if PHYSICS {
+                    node.get_physics_process_delta_time()
+                } else {
+                    node.get_process_delta_time()
+                };
+                (self.cb)(app_state, delta);
+                // one undo step per frame would bury everything else in the history
+                MessageResult::Untracked
+            }
+            msg => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal(signal_name(PHYSICS));
+    }
+}
+
+impl<N, State: ArgTuple, Cb, Inner, const PHYSICS: bool> ElementView<N, State>
+    for OnProcess<N, Cb, Inner, PHYSICS>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State, f64),
+    N: Inherits<Node>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Cb0, Inner, const PHYSICS0: bool> OnProcess<N, Cb0, Inner, PHYSICS0> {
+    impl_element_view! { N }
+}
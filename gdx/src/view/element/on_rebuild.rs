@@ -3,8 +3,8 @@ use std::marker::PhantomData;
 use godot::{builtin::Variant, classes::Node, meta::ToGodot, obj::Inherits, prelude::Gd};
 
 use crate::{
-    AnchorType, ElementView, Message, MessageResult, View,
-    ctx::FullMessage,
+    AnchorType, ElementView, MessageResult, View,
+    inspect::Inspector,
     view::{ArgTuple, element::impl_element_view},
 };
 
@@ -91,6 +91,10 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.inner.collect_nodes(&state.inner_view_state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+    }
 }
 
 impl<N, State: ArgTuple, Cb, Inner> ElementView<N, State> for OnRebuild<N, Cb, Inner>
@@ -2,7 +2,7 @@ use godot::{
     builtin::{Callable, Variant},
     classes::Node,
     meta::ToGodot,
-    obj::{Inherits, NewAlloc},
+    obj::Inherits,
     prelude::Gd,
 };
 use std::{marker::PhantomData, sync::Arc};
@@ -10,6 +10,7 @@ use std::{marker::PhantomData, sync::Arc};
 use crate::{
     AnchorType, ElementView, Message, MessageResult, View, ViewID,
     ctx::FullMessage,
+    inspect::Inspector,
     view::{ArgTuple, element::impl_element_view},
 };
 
@@ -58,6 +59,7 @@ where
         });
 
         node.upcast_mut().connect(self.name.as_ref(), &callable);
+        ctx.stats.signals_connected += 1;
         OnSignalViewState {
             callable,
             inner_view_state,
@@ -85,6 +87,7 @@ where
 
         node.upcast_mut()
             .disconnect(prev.name.as_ref(), &state.callable);
+        ctx.stats.signals_disconnected += 1;
 
         let msgs = ctx.msg_queue.clone();
         let path: Arc<[ViewID]> = ctx.path.clone().into();
@@ -100,6 +103,7 @@ where
         });
 
         node.upcast_mut().connect(self.name.as_ref(), &callable);
+        ctx.stats.signals_connected += 1;
         state.callable = callable;
     }
 
@@ -111,6 +115,13 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        // the node outlives the view, so it must not send messages anymore
+        if ctx.keep_nodes || ctx.pooling() {
+            let mut node = self.get_node(state);
+            node.upcast_mut()
+                .disconnect(self.name.as_ref(), &state.callable);
+            ctx.stats.signals_disconnected += 1;
+        }
         self.inner.teardown(
             &mut state.inner_view_state,
             ctx,
@@ -129,12 +140,10 @@ where
     ) -> MessageResult {
         if path.is_empty() {
             match msg {
-                Message::Signal { ref name, ref args } => {
-                    if **name == *self.name.as_ref() {
-                        let node = self.get_node(view_state);
-                        (self.cb)(app_state, args, node);
-                        return MessageResult::Success;
-                    }
+                Message::Signal { ref name, ref args } if **name == *self.name.as_ref() => {
+                    let node = self.get_node(view_state);
+                    (self.cb)(app_state, args, node);
+                    return MessageResult::Success;
                 }
                 _ => {}
             }
@@ -146,6 +155,11 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.inner.collect_nodes(&state.inner_view_state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal(self.name.as_ref());
+    }
 }
 
 impl<N, State: ArgTuple, Name, Cb, Inner> ElementView<N, State> for OnSignal<N, Name, Cb, Inner>
@@ -10,13 +10,14 @@ use std::marker::PhantomData;
 use crate::{
     ElementView, ViewID,
     ctx::{Message, MessageResult},
+    inspect::Inspector,
     view::{AnchorType, ArgTuple, View, element::impl_element_view},
 };
 
 macro_rules! theme_override_types {
     (
         $(
-            $(($ref:tt))? $name:ident: $typ:ty,
+            $(($ref:tt))? $name:ident: $typ:ty => $prefix:literal,
         )*
     ) => {
         paste::paste! {
@@ -26,6 +27,7 @@ macro_rules! theme_override_types {
 
                 impl ThemeOverrideType for [< ThemeOverride $name:camel >] {
                     type ValueType = $typ;
+                    const PROPERTY_PREFIX: &str = $prefix;
 
                     fn set(node: &mut Control, name: impl AsArg<StringName>, value: Self::ValueType) {
                         node.[< add_theme_ $name _override >](name, $($ref)? value);
@@ -39,17 +41,18 @@ macro_rules! theme_override_types {
     };
 }
 pub trait ThemeOverrideType: Copy {
-    type ValueType: Clone + PartialEq;
+    type ValueType: Clone + PartialEq + ToGodot;
+    const PROPERTY_PREFIX: &str;
     fn set(node: &mut Control, name: impl AsArg<StringName>, value: Self::ValueType);
     fn remove(node: &mut Control, name: impl AsArg<StringName>);
 }
 theme_override_types! {
-    color: Color,
-    constant: i32,
-    (&)font: Gd<Font>,
-    font_size: i32,
-    (&)icon: Gd<Texture2D>,
-    (&)stylebox: Gd<StyleBox>,
+    color: Color => "theme_override_colors",
+    constant: i32 => "theme_override_constants",
+    (&)font: Gd<Font> => "theme_override_fonts",
+    font_size: i32 => "theme_override_font_sizes",
+    (&)icon: Gd<Texture2D> => "theme_override_icons",
+    (&)stylebox: Gd<StyleBox> => "theme_override_styles",
 }
 
 pub struct ThemeOverride<N, Typ: ThemeOverrideType, Name, Inner> {
@@ -82,6 +85,7 @@ where
         let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
         let mut node = self.inner.get_node(&inner_view_state);
         Typ::set(node.upcast_mut(), self.name.as_ref(), self.value.clone());
+        ctx.stats.attrs_written += 1;
         ThemeOverrideViewState { inner_view_state }
     }
 
@@ -106,8 +110,10 @@ where
         let mut node = self.get_node(state);
         if self.name.as_ref() != prev.name.as_ref() {
             Typ::remove(node.upcast_mut(), prev.name.as_ref());
+            ctx.stats.attrs_written += 1;
         }
         Typ::set(node.upcast_mut(), self.name.as_ref(), self.value.clone());
+        ctx.stats.attrs_written += 1;
     }
 
     fn teardown(
@@ -118,6 +124,11 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        if ctx.pooling() {
+            let mut node = self.get_node(state);
+            Typ::remove(node.upcast_mut(), self.name.as_ref());
+            ctx.stats.attrs_written += 1;
+        }
         self.inner.teardown(
             &mut state.inner_view_state,
             ctx,
@@ -141,6 +152,14 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.inner.collect_nodes(&state.inner_view_state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr(
+            format!("{}/{}", Typ::PROPERTY_PREFIX, self.name.as_ref()),
+            self.value.to_variant().to_string(),
+        );
+    }
 }
 
 impl<State: ArgTuple, N, Typ, Name, Inner> ElementView<N, State>
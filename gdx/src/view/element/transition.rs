@@ -0,0 +1,318 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use godot::{
+    builtin::{Callable, Variant, Vector2},
+    classes::{
+        Node, Tween,
+        tween::{EaseType, TransitionType},
+    },
+    meta::ToGodot,
+    obj::Inherits,
+    prelude::Gd,
+};
+
+use crate::{
+    AnchorType, ElementView, MessageResult, View,
+    inspect::Inspector,
+    view::{ArgTuple, element::impl_element_view},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TweenSpec {
+    pub duration: f64,
+    pub delay: f64,
+    pub trans: TransitionType,
+    pub ease: EaseType,
+}
+
+pub fn tween(duration: f64) -> TweenSpec {
+    TweenSpec {
+        duration,
+        delay: 0.0,
+        trans: TransitionType::LINEAR,
+        ease: EaseType::IN_OUT,
+    }
+}
+impl TweenSpec {
+    pub fn delay(mut self, delay: f64) -> Self {
+        self.delay = delay;
+        self
+    }
+    pub fn trans(mut self, trans: TransitionType) -> Self {
+        self.trans = trans;
+        self
+    }
+    pub fn ease(mut self, ease: EaseType) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Tweens `property` (which can be a subpath like `modulate:a`) to `to`,
+    /// jumping to `from` first if given.
+    pub fn tween_property(
+        &self,
+        tween: &mut Gd<Tween>,
+        node: &mut Gd<Node>,
+        property: &str,
+        from: Option<Variant>,
+        to: Variant,
+    ) {
+        if let Some(from) = from {
+            node.set_indexed(property, &from);
+        }
+        if let Some(mut tweener) = tween.tween_property(&*node, property, &to, self.duration) {
+            tweener.set_delay(self.delay);
+            tweener.set_trans(self.trans);
+            tweener.set_ease(self.ease);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    Enter,
+    Exit,
+}
+
+type CustomTransition = Rc<dyn Fn(&mut Gd<Tween>, &mut Gd<Node>, TransitionPhase)>;
+
+#[derive(Clone)]
+pub enum Transition {
+    /// Tweens `modulate:a` from/to 0.
+    Fade(TweenSpec),
+    /// Tweens `position` from/to the current position plus an offset.
+    Slide(Vector2, TweenSpec),
+    /// Tweens `scale` from/to the given scale.
+    Scale(Vector2, TweenSpec),
+    /// Adds whatever tweeners it wants to the tween, the node gets freed once the exit tween finishes.
+    Custom(CustomTransition),
+}
+
+impl Transition {
+    pub fn fade(spec: TweenSpec) -> Self {
+        Self::Fade(spec)
+    }
+    pub fn slide(offset: Vector2, spec: TweenSpec) -> Self {
+        Self::Slide(offset, spec)
+    }
+    pub fn scale(from: f32, spec: TweenSpec) -> Self {
+        Self::Scale(Vector2::new(from, from), spec)
+    }
+    pub fn custom(f: impl Fn(&mut Gd<Tween>, &mut Gd<Node>, TransitionPhase) + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    /// The property the transition tweens, `None` for custom ones since they can touch anything.
+    fn tweened_property(&self) -> Option<&'static str> {
+        match self {
+            Transition::Fade(_) => Some("modulate"),
+            Transition::Slide(..) => Some("position"),
+            Transition::Scale(..) => Some("scale"),
+            Transition::Custom(_) => None,
+        }
+    }
+
+    fn apply(&self, tween: &mut Gd<Tween>, node: &mut Gd<Node>, phase: TransitionPhase) {
+        let enter = phase == TransitionPhase::Enter;
+        match self {
+            Transition::Fade(spec) => {
+                let current = node.get_indexed("modulate:a");
+                if enter {
+                    spec.tween_property(tween, node, "modulate:a", Some(0.0.to_variant()), current);
+                } else {
+                    spec.tween_property(tween, node, "modulate:a", None, 0.0.to_variant());
+                }
+            }
+            Transition::Slide(offset, spec) => {
+                let current = node.get("position").try_to::<Vector2>().unwrap_or_default();
+                let moved = (current + *offset).to_variant();
+                if enter {
+                    spec.tween_property(tween, node, "position", Some(moved), current.to_variant());
+                } else {
+                    spec.tween_property(tween, node, "position", None, moved);
+                }
+            }
+            Transition::Scale(scale, spec) => {
+                let current = node.get("scale");
+                if enter {
+                    spec.tween_property(tween, node, "scale", Some(scale.to_variant()), current);
+                } else {
+                    spec.tween_property(tween, node, "scale", None, scale.to_variant());
+                }
+            }
+            Transition::Custom(f) => f(tween, node, phase),
+        }
+    }
+}
+
+pub struct WithTransition<N, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) enter: Option<Transition>,
+    pub(crate) exit: Option<Transition>,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct WithTransitionViewState<InnerViewState> {
+    tween: Option<Gd<Tween>>,
+    // the value before entering, so a node parked mid-tween goes back into the pool untouched
+    settled: Option<(&'static str, Variant)>,
+    // false after a custom enter, we don't know what it changed so the nodes don't get parked
+    parkable: bool,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Inner> View<State> for WithTransition<N, Inner>
+where
+    Inner: ElementView<N, State>,
+    N: Inherits<Node>,
+{
+    type ViewState = WithTransitionViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+
+        let mut settled = None;
+        let parkable = self
+            .enter
+            .as_ref()
+            .is_none_or(|enter| enter.tweened_property().is_some());
+        let tween = self.enter.as_ref().and_then(|enter| {
+            let mut node = self.inner.get_node(&inner_view_state).upcast::<Node>();
+            if ctx.node_pool.is_some() {
+                settled = enter.tweened_property().map(|name| (name, node.get(name)));
+            }
+            let mut tween = node.create_tween()?;
+            tween.set_parallel();
+            enter.apply(&mut tween, &mut node, TransitionPhase::Enter);
+            Some(tween)
+        });
+
+        WithTransitionViewState {
+            tween,
+            settled,
+            parkable,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self
+            .inner
+            .get_node(&state.inner_view_state)
+            .upcast::<Node>();
+        if let Some(mut tween) = state.tween.take()
+            && tween.is_valid()
+        {
+            tween.kill();
+        }
+        if ctx.pooling()
+            && let Some((name, value)) = &state.settled
+        {
+            node.set(*name, value);
+            ctx.stats.attrs_written += 1;
+        }
+
+        // if an ancestor is already lingering for its own exit we just go down with it
+        let exit = self
+            .exit
+            .as_ref()
+            .filter(|_| !ctx.keep_nodes && node.is_inside_tree());
+
+        ctx.keep_nodes = exit.is_some() || ctx.keep_nodes;
+        let pool = if ctx.pooling() && !state.parkable {
+            ctx.node_pool.take()
+        } else {
+            None
+        };
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        if pool.is_some() {
+            ctx.node_pool = pool;
+        }
+
+        if let Some(exit) = exit {
+            ctx.keep_nodes = false;
+            match node.create_tween() {
+                Some(mut tween) => {
+                    tween.set_parallel();
+                    exit.apply(&mut tween, &mut node, TransitionPhase::Exit);
+                    tween.connect(
+                        "finished",
+                        &Callable::from_object_method(&node, "queue_free"),
+                    );
+                }
+                None => node.queue_free(),
+            }
+        }
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        self.inner
+            .message(msg, path, &mut view_state.inner_view_state, app_state)
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+    }
+}
+
+impl<N, State: ArgTuple, Inner> ElementView<N, State> for WithTransition<N, Inner>
+where
+    Inner: ElementView<N, State>,
+    N: Inherits<Node>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Inner> WithTransition<N, Inner> {
+    impl_element_view! { N }
+}
@@ -0,0 +1,311 @@
+use godot::{
+    builtin::{Variant, Vector2},
+    classes::{CanvasItem, Control, Node},
+    meta::ToGodot,
+    obj::Inherits,
+    prelude::Gd,
+};
+use std::{marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    AnchorType, ElementView, Message, MessageResult, View, ViewID,
+    classes::{CanDropFn, Overrides, TooltipFn, VirtualHost},
+    ctx::FullMessage,
+    inspect::Inspector,
+    view::{ArgTuple, element::impl_element_view},
+};
+
+#[derive(Clone)]
+pub enum VirtualFn {
+    GetDragData(Rc<dyn Fn(Vector2) -> Variant>),
+    CanDropData(CanDropFn),
+    MakeCustomTooltip(TooltipFn),
+    HasPoint(Rc<dyn Fn(Vector2) -> bool>),
+    GetMinimumSize(Rc<dyn Fn() -> Vector2>),
+    Draw(Rc<dyn Fn(Gd<CanvasItem>)>),
+}
+
+impl VirtualFn {
+    fn name(&self) -> &'static str {
+        match self {
+            VirtualFn::GetDragData(_) => "_get_drag_data",
+            VirtualFn::CanDropData(_) => "_can_drop_data",
+            VirtualFn::MakeCustomTooltip(_) => "_make_custom_tooltip",
+            VirtualFn::HasPoint(_) => "_has_point",
+            VirtualFn::GetMinimumSize(_) => "_get_minimum_size",
+            VirtualFn::Draw(_) => "_draw",
+        }
+    }
+
+    fn set(&self, overrides: &mut Overrides, install: bool) {
+        match self {
+            VirtualFn::GetDragData(f) => overrides.get_drag_data = install.then(|| f.clone()),
+            VirtualFn::CanDropData(f) => overrides.can_drop_data = install.then(|| f.clone()),
+            VirtualFn::MakeCustomTooltip(f) => {
+                overrides.make_custom_tooltip = install.then(|| f.clone())
+            }
+            VirtualFn::HasPoint(f) => overrides.has_point = install.then(|| f.clone()),
+            VirtualFn::GetMinimumSize(f) => overrides.get_minimum_size = install.then(|| f.clone()),
+            VirtualFn::Draw(f) => overrides.draw = install.then(|| f.clone()),
+        }
+    }
+
+    fn apply<N: VirtualHost>(&self, node: &mut Gd<N>, install: bool) {
+        N::with_overrides(node, |overrides| self.set(overrides, install));
+        // godot caches these, so it has to be told that they changed
+        match self {
+            VirtualFn::GetMinimumSize(_) => {
+                if let Ok(mut control) = node.clone().upcast::<Node>().try_cast::<Control>() {
+                    control.update_minimum_size();
+                }
+            }
+            VirtualFn::Draw(_) => node.clone().upcast::<CanvasItem>().queue_redraw(),
+            _ => {}
+        }
+    }
+}
+
+/// Overrides one virtual of a [`GdxControl`](crate::GdxControl) or [`GdxNode2D`](crate::GdxNode2D).
+/// The closure is swapped in again on every rebuild, so it can capture values from the view.
+pub struct Virtual<N, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) f: VirtualFn,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct VirtualViewState<InnerViewState> {
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Inner> View<State> for Virtual<N, Inner>
+where
+    Inner: ElementView<N, State>,
+    N: VirtualHost,
+{
+    type ViewState = VirtualViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+        self.f.apply(&mut node, true);
+        VirtualViewState { inner_view_state }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        let mut node = self.get_node(state);
+        self.f.apply(&mut node, true);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state);
+        N::with_overrides(&mut node, |overrides| self.f.set(overrides, false));
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        self.inner
+            .message(msg, path, &mut view_state.inner_view_state, app_state)
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr(self.f.name(), "override");
+    }
+}
+
+impl<N, State: ArgTuple, Inner> ElementView<N, State> for Virtual<N, Inner>
+where
+    Inner: ElementView<N, State>,
+    N: VirtualHost,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Inner> Virtual<N, Inner> {
+    impl_element_view! { N }
+}
+
+/// Routes `_drop_data` through the message queue, so the callback gets the state.
+pub struct OnDropData<N, Cb, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) cb: Cb,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct OnDropDataViewState<InnerViewState> {
+    id: ViewID,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Cb, Inner> View<State> for OnDropData<N, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State, Vector2, Variant),
+    N: VirtualHost + Inherits<Control>,
+{
+    type ViewState = OnDropDataViewState<Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+
+        let id = ctx.new_structural_id();
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        N::with_overrides(&mut node, |overrides| {
+            overrides.drop_data = Some(Rc::new(move |at_position, data| {
+                msgs.lock().push_back(FullMessage {
+                    msg: Message::Signal {
+                        name: "drop_data".into(),
+                        args: Box::new([at_position.to_variant(), data]),
+                    },
+                    path: path.clone(),
+                });
+            }));
+        });
+
+        OnDropDataViewState {
+            id,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state);
+        N::with_overrides(&mut node, |overrides| overrides.drop_data = None);
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[crate::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { args, .. }, Some((start, []))) if *start == view_state.id => {
+                if let [at_position, data] = &*args {
+                    (self.cb)(
+                        app_state,
+                        at_position.try_to().unwrap_or_default(),
+                        data.clone(),
+                    );
+                }
+                MessageResult::Success
+            }
+            (msg, _) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.attr("_drop_data", "override");
+    }
+}
+
+impl<N, State: ArgTuple, Cb, Inner> ElementView<N, State> for OnDropData<N, Cb, Inner>
+where
+    Inner: ElementView<N, State>,
+    Cb: Fn(&mut State, Vector2, Variant),
+    N: VirtualHost + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Cb0, Inner> OnDropData<N, Cb0, Inner> {
+    impl_element_view! { N }
+}
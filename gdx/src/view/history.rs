@@ -0,0 +1,149 @@
+use std::{hash::Hash, marker::PhantomData, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{
+    ArgTuple, Message, View,
+    ctx::{FullMessage, MsgQueue},
+    history::{HistoryAction, HistoryOp, HistoryStatus},
+    inspect::Inspector,
+    util::hash,
+};
+
+#[derive(Clone)]
+pub struct HistoryHandle {
+    pub(crate) queue: MsgQueue,
+    pub(crate) status: Arc<Mutex<HistoryStatus>>,
+}
+impl HistoryHandle {
+    fn push(&self, action: HistoryAction) {
+        self.queue.lock().push_back(FullMessage {
+            msg: Message::History(action),
+            path: Arc::new([]),
+        });
+    }
+
+    pub fn undo(&self) {
+        self.push(HistoryAction::Undo);
+    }
+    pub fn redo(&self) {
+        self.push(HistoryAction::Redo);
+    }
+    pub fn can_undo(&self) -> bool {
+        self.status.lock().can_undo
+    }
+    pub fn can_redo(&self) -> bool {
+        self.status.lock().can_redo
+    }
+    /// Merges the current message batch into the previous history entry if that one
+    /// was coalesced with the same key recently enough (e.g. while a slider is dragged).
+    pub fn coalesce<K: Hash>(&self, key: K) {
+        self.push(HistoryAction::Coalesce(hash(&key)));
+    }
+    /// Records an inverse operation for the current message batch instead of a snapshot.
+    pub fn record<State: 'static>(
+        &self,
+        undo: impl Fn(&mut State) + 'static,
+        redo: impl Fn(&mut State) + 'static,
+    ) {
+        self.push(HistoryAction::Record(Box::new(HistoryOp {
+            undo: Box::new(undo),
+            redo: Box::new(redo),
+        })));
+    }
+}
+
+pub struct WithHistory<State, InnerFn> {
+    inner_fn: InnerFn,
+    _p: PhantomData<State>,
+}
+
+impl<State: ArgTuple, InnerFn, Inner> View<State> for WithHistory<State, InnerFn>
+where
+    InnerFn: Fn(HistoryHandle) -> Inner,
+    Inner: View<State>,
+{
+    type ViewState = (Inner, Inner::ViewState);
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let handle = HistoryHandle {
+            queue: ctx.msg_queue.clone(),
+            status: ctx.history.clone(),
+        };
+        let inner = (self.inner_fn)(handle);
+        let vstate = inner.build(ctx, anchor, anchor_type, app_state);
+        (inner, vstate)
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) {
+        let handle = HistoryHandle {
+            queue: ctx.msg_queue.clone(),
+            status: ctx.history.clone(),
+        };
+        let inner = (self.inner_fn)(handle);
+        inner.rebuild(&state.0, &mut state.1, ctx, anchor, anchor_type, app_state);
+        state.0 = inner;
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) {
+        state
+            .0
+            .teardown(&mut state.1, ctx, anchor, anchor_type, app_state);
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[super::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> crate::MessageResult {
+        view_state
+            .0
+            .message(msg, path, &mut view_state.1, app_state)
+    }
+
+    fn collect_nodes(
+        &self,
+        state: &Self::ViewState,
+        nodes: &mut Vec<godot::prelude::Gd<godot::prelude::Node>>,
+    ) {
+        state.0.collect_nodes(&state.1, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        state.0.inspect(&state.1, out);
+    }
+}
+
+pub fn history<State: ArgTuple, InnerFn, Inner>(inner_fn: InnerFn) -> WithHistory<State, InnerFn>
+where
+    Inner: View<State>,
+    InnerFn: Fn(HistoryHandle) -> Inner,
+{
+    WithHistory {
+        inner_fn,
+        _p: PhantomData,
+    }
+}
@@ -0,0 +1,377 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use godot::{
+    builtin::StringName,
+    classes::{InputEvent, InputEventKey, Node, Os},
+    global::{Key, godot_error},
+    meta::ToGodot,
+    obj::{Gd, NewAlloc, Singleton},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, Message, MessageResult, View, ViewID,
+    classes::{GdxInputListener, InputHandler},
+    ctx::{FullMessage, MsgQueue},
+    inspect::Inspector,
+    trace::trace_span,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyCombo {
+    key: Key,
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+    meta: bool,
+    cmd_or_ctrl: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ShortcutError {
+    UnknownModifier(String),
+    UnknownKey(String),
+}
+
+impl std::fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShortcutError::UnknownModifier(part) => write!(f, "unknown modifier `{part}`"),
+            ShortcutError::UnknownKey(key) => write!(f, "unknown key `{key}`"),
+        }
+    }
+}
+
+impl KeyCombo {
+    /// Parses things like `ctrl+shift+z`, `escape` or `cmd+s` (ctrl, or command on macOS),
+    /// looking the key itself up with `find_key`.
+    fn parse(keys: &str, find_key: impl Fn(&str) -> Key) -> Result<Self, ShortcutError> {
+        let mut combo = KeyCombo {
+            key: Key::NONE,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+            cmd_or_ctrl: false,
+        };
+        let mut parts = keys.split('+').map(str::trim).collect::<Vec<_>>();
+        let key = parts.pop().unwrap_or_default();
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => combo.ctrl = true,
+                "shift" => combo.shift = true,
+                "alt" | "option" => combo.alt = true,
+                "meta" | "super" | "win" => combo.meta = true,
+                "cmd" | "command" => combo.cmd_or_ctrl = true,
+                _ => return Err(ShortcutError::UnknownModifier(part.into())),
+            }
+        }
+        combo.key = find_key(key);
+        if combo.key == Key::NONE {
+            return Err(ShortcutError::UnknownKey(key.into()));
+        }
+        Ok(combo)
+    }
+
+    fn matches(&self, event: &Gd<InputEventKey>) -> bool {
+        let ctrl = if self.cmd_or_ctrl {
+            event.is_command_or_control_pressed()
+        } else {
+            event.is_ctrl_pressed() == self.ctrl
+        };
+        event.get_keycode() == self.key
+            && ctrl
+            && event.is_shift_pressed() == self.shift
+            && event.is_alt_pressed() == self.alt
+            && (self.cmd_or_ctrl || event.is_meta_pressed() == self.meta)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum InputMatcher {
+    Shortcut(KeyCombo),
+    Action(StringName),
+    Never,
+}
+
+impl InputMatcher {
+    fn matches(&self, event: &Gd<InputEvent>, echo: bool) -> bool {
+        match self {
+            InputMatcher::Shortcut(combo) => {
+                event
+                    .clone()
+                    .try_cast::<InputEventKey>()
+                    .is_ok_and(|event| {
+                        event.is_pressed() && (echo || !event.is_echo()) && combo.matches(&event)
+                    })
+            }
+            InputMatcher::Action(action) => {
+                event.is_action_pressed_ex(action).allow_echo(echo).done()
+            }
+            InputMatcher::Never => false,
+        }
+    }
+}
+
+/// Listens to unhandled input while mounted. Since godot hands unhandled input to the
+/// nodes further down the tree first, a listener in an open dialog gets to a key before the ones behind it.
+pub struct InputListener<State, Keys, Cb> {
+    keys: Keys,
+    action: bool,
+    echo: bool,
+    consume: bool,
+    cb: Cb,
+    _p: PhantomData<State>,
+}
+
+impl<State, Keys, Cb> InputListener<State, Keys, Cb> {
+    /// Also fire for held keys repeating.
+    pub fn echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+    /// Whether a matching event is marked as handled, so nothing else sees it. On by default.
+    pub fn consume(mut self, consume: bool) -> Self {
+        self.consume = consume;
+        self
+    }
+}
+
+impl<State, Keys: AsRef<str>, Cb> InputListener<State, Keys, Cb> {
+    fn matcher(&self) -> InputMatcher {
+        if self.action {
+            InputMatcher::Action(self.keys.as_ref().into())
+        } else {
+            let keys = self.keys.as_ref();
+            match KeyCombo::parse(keys, |key| Os::singleton().find_keycode_from_string(key)) {
+                Ok(combo) => InputMatcher::Shortcut(combo),
+                Err(err) => {
+                    godot_error!("Invalid shortcut `{keys}`: {err}");
+                    InputMatcher::Never
+                }
+            }
+        }
+    }
+
+    fn handler(&self, msgs: MsgQueue, path: Arc<[ViewID]>) -> InputHandler {
+        let matcher = self.matcher();
+        let echo = self.echo;
+        let consume = self.consume;
+        Box::new(move |event| {
+            if !matcher.matches(event, echo) {
+                return false;
+            }
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "unhandled_input".into(),
+                    args: Box::new([event.to_variant()]),
+                },
+                path: path.clone(),
+            });
+            consume
+        })
+    }
+}
+
+pub struct InputListenerViewState {
+    listener: Gd<GdxInputListener>,
+}
+
+impl<State: ArgTuple, Keys, Cb> View<State> for InputListener<State, Keys, Cb>
+where
+    Keys: AsRef<str>,
+    Cb: Fn(&mut State),
+{
+    type ViewState = InputListenerViewState;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "InputListener");
+        let mut listener = GdxInputListener::new_alloc();
+        listener.bind_mut().handler =
+            Some(self.handler(ctx.msg_queue.clone(), ctx.path.clone().into()));
+        anchor_type.add(anchor, &listener.clone().upcast());
+        ctx.stats.nodes_created += 1;
+        InputListenerViewState { listener }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "InputListener");
+        if self.keys.as_ref() != prev.keys.as_ref()
+            || self.action != prev.action
+            || self.echo != prev.echo
+            || self.consume != prev.consume
+        {
+            state.listener.bind_mut().handler =
+                Some(self.handler(ctx.msg_queue.clone(), ctx.path.clone().into()));
+        }
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "InputListener");
+        state.listener.bind_mut().handler = None;
+        anchor_type.remove(anchor, &state.listener.clone().upcast());
+        state.listener.queue_free();
+        ctx.stats.nodes_freed += 1;
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        _view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match msg {
+            Message::Signal { ref name, .. } if path.is_empty() && &**name == "unhandled_input" => {
+                (self.cb)(app_state);
+                MessageResult::Success
+            }
+            msg => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        nodes.push(state.listener.clone().upcast());
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view(
+            if self.action { "Action" } else { "Shortcut" },
+            &[state.listener.clone().upcast()],
+            |_| {},
+        );
+        out.attr("keys", self.keys.as_ref());
+    }
+}
+
+/// Calls `cb` when the key combination, e.g. `ctrl+s`, is pressed and nothing handled it before.
+/// A combination that doesn't parse is reported as a godot error and never fires.
+pub fn shortcut<State: ArgTuple, Keys, Cb>(keys: Keys, cb: Cb) -> InputListener<State, Keys, Cb>
+where
+    Keys: AsRef<str>,
+    Cb: Fn(&mut State),
+{
+    InputListener {
+        keys,
+        action: false,
+        echo: false,
+        consume: true,
+        cb,
+        _p: PhantomData,
+    }
+}
+
+/// Calls `cb` when the `InputMap` action is pressed and nothing handled it before.
+pub fn on_action<State: ArgTuple, Action, Cb>(
+    action: Action,
+    cb: Cb,
+) -> InputListener<State, Action, Cb>
+where
+    Action: AsRef<str>,
+    Cb: Fn(&mut State),
+{
+    InputListener {
+        keys: action,
+        action: true,
+        echo: false,
+        consume: true,
+        cb,
+        _p: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_key(key: &str) -> Key {
+        match key.to_lowercase().as_str() {
+            "z" => Key::Z,
+            "escape" => Key::ESCAPE,
+            _ => Key::NONE,
+        }
+    }
+
+    fn combo(key: Key) -> KeyCombo {
+        KeyCombo {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+            cmd_or_ctrl: false,
+        }
+    }
+
+    #[test]
+    fn plain_key() {
+        assert_eq!(KeyCombo::parse("escape", find_key), Ok(combo(Key::ESCAPE)));
+    }
+
+    #[test]
+    fn modifiers() {
+        assert_eq!(
+            KeyCombo::parse("Ctrl + shift+Z", find_key),
+            Ok(KeyCombo {
+                ctrl: true,
+                shift: true,
+                ..combo(Key::Z)
+            })
+        );
+        assert_eq!(
+            KeyCombo::parse("option+win+z", find_key),
+            Ok(KeyCombo {
+                alt: true,
+                meta: true,
+                ..combo(Key::Z)
+            })
+        );
+        assert_eq!(
+            KeyCombo::parse("cmd+z", find_key),
+            Ok(KeyCombo {
+                cmd_or_ctrl: true,
+                ..combo(Key::Z)
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_modifier() {
+        assert_eq!(
+            KeyCombo::parse("hyper+z", find_key),
+            Err(ShortcutError::UnknownModifier("hyper".into()))
+        );
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert_eq!(
+            KeyCombo::parse("ctrl+nope", find_key),
+            Err(ShortcutError::UnknownKey("nope".into()))
+        );
+        assert_eq!(
+            KeyCombo::parse("", find_key),
+            Err(ShortcutError::UnknownKey("".into()))
+        );
+    }
+}
@@ -0,0 +1,694 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{ItemList, MenuButton, Node, Object, OptionButton, PopupMenu, Texture2D},
+    meta::ToGodot,
+    obj::{Gd, GodotClass, Inherits, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage, inspect::Inspector, trace::trace_span, view::element::impl_element_view,
+};
+
+/// Controls that keep a flat list of items through `set_item_*` instead of child nodes.
+pub trait ItemHost: GodotClass + Inherits<Node> {
+    /// The node and signal reporting a picked item.
+    fn item_signal(this: &Gd<Self>) -> (Gd<Object>, &'static str);
+    /// Turns the argument of the [`item_signal`](Self::item_signal) into the item's index.
+    fn item_index(_this: &Gd<Self>, arg: i32) -> i32 {
+        arg
+    }
+    fn set_item_count(this: &mut Gd<Self>, count: i32);
+    fn set_item_text(this: &mut Gd<Self>, idx: i32, text: &str);
+    fn set_item_icon(this: &mut Gd<Self>, idx: i32, icon: Option<&Gd<Texture2D>>);
+    fn set_item_disabled(this: &mut Gd<Self>, idx: i32, disabled: bool);
+    /// `None` makes the item a plain one again. Only menus have checkable items.
+    fn set_item_checked(_this: &mut Gd<Self>, _idx: i32, _checked: Option<bool>) {}
+    fn set_item_id(_this: &mut Gd<Self>, _idx: i32, _id: i32) {}
+    /// Only menus have submenus.
+    fn set_item_submenu(_this: &mut Gd<Self>, _idx: i32, _submenu: Option<&Gd<PopupMenu>>) {}
+    /// Menus don't keep a selection.
+    fn selected_items(_this: &Gd<Self>) -> Vec<i32> {
+        vec![]
+    }
+    fn select_items(_this: &mut Gd<Self>, _items: &[i32]) {}
+}
+
+impl ItemHost for ItemList {
+    fn item_signal(this: &Gd<Self>) -> (Gd<Object>, &'static str) {
+        (this.clone().upcast(), "item_selected")
+    }
+    fn set_item_count(this: &mut Gd<Self>, count: i32) {
+        this.set_item_count(count);
+    }
+    fn set_item_text(this: &mut Gd<Self>, idx: i32, text: &str) {
+        this.set_item_text(idx, text);
+    }
+    fn set_item_icon(this: &mut Gd<Self>, idx: i32, icon: Option<&Gd<Texture2D>>) {
+        this.set_item_icon(idx, icon);
+    }
+    fn set_item_disabled(this: &mut Gd<Self>, idx: i32, disabled: bool) {
+        this.set_item_disabled(idx, disabled);
+    }
+    fn selected_items(this: &Gd<Self>) -> Vec<i32> {
+        this.clone().get_selected_items().to_vec()
+    }
+    fn select_items(this: &mut Gd<Self>, items: &[i32]) {
+        this.deselect_all();
+        for idx in items {
+            this.select_ex(*idx).single(false).done();
+        }
+    }
+}
+
+impl ItemHost for OptionButton {
+    fn item_signal(this: &Gd<Self>) -> (Gd<Object>, &'static str) {
+        (this.clone().upcast(), "item_selected")
+    }
+    fn set_item_count(this: &mut Gd<Self>, count: i32) {
+        this.set_item_count(count);
+    }
+    fn set_item_text(this: &mut Gd<Self>, idx: i32, text: &str) {
+        this.set_item_text(idx, text);
+    }
+    fn set_item_icon(this: &mut Gd<Self>, idx: i32, icon: Option<&Gd<Texture2D>>) {
+        this.set_item_icon(idx, icon);
+    }
+    fn set_item_disabled(this: &mut Gd<Self>, idx: i32, disabled: bool) {
+        this.set_item_disabled(idx, disabled);
+    }
+    fn set_item_id(this: &mut Gd<Self>, idx: i32, id: i32) {
+        this.set_item_id(idx, id);
+    }
+    fn selected_items(this: &Gd<Self>) -> Vec<i32> {
+        Some(this.get_selected())
+            .filter(|idx| *idx >= 0)
+            .into_iter()
+            .collect()
+    }
+    fn select_items(this: &mut Gd<Self>, items: &[i32]) {
+        this.select(items.first().copied().unwrap_or(-1));
+    }
+}
+
+impl ItemHost for PopupMenu {
+    fn item_signal(this: &Gd<Self>) -> (Gd<Object>, &'static str) {
+        (this.clone().upcast(), "id_pressed")
+    }
+    fn item_index(this: &Gd<Self>, arg: i32) -> i32 {
+        this.get_item_index(arg)
+    }
+    fn set_item_count(this: &mut Gd<Self>, count: i32) {
+        this.set_item_count(count);
+    }
+    fn set_item_text(this: &mut Gd<Self>, idx: i32, text: &str) {
+        this.set_item_text(idx, text);
+    }
+    fn set_item_icon(this: &mut Gd<Self>, idx: i32, icon: Option<&Gd<Texture2D>>) {
+        this.set_item_icon(idx, icon);
+    }
+    fn set_item_disabled(this: &mut Gd<Self>, idx: i32, disabled: bool) {
+        this.set_item_disabled(idx, disabled);
+    }
+    fn set_item_checked(this: &mut Gd<Self>, idx: i32, checked: Option<bool>) {
+        this.set_item_as_checkable(idx, checked.is_some());
+        this.set_item_checked(idx, checked.unwrap_or(false));
+    }
+    fn set_item_id(this: &mut Gd<Self>, idx: i32, id: i32) {
+        this.set_item_id(idx, id);
+    }
+    fn set_item_submenu(this: &mut Gd<Self>, idx: i32, submenu: Option<&Gd<PopupMenu>>) {
+        this.set_item_submenu_node(idx, submenu);
+    }
+}
+
+// the items live in the button's popup
+impl ItemHost for MenuButton {
+    fn item_signal(this: &Gd<Self>) -> (Gd<Object>, &'static str) {
+        <PopupMenu as ItemHost>::item_signal(&this.get_popup().unwrap())
+    }
+    fn item_index(this: &Gd<Self>, arg: i32) -> i32 {
+        <PopupMenu as ItemHost>::item_index(&this.get_popup().unwrap(), arg)
+    }
+    fn set_item_count(this: &mut Gd<Self>, count: i32) {
+        <PopupMenu as ItemHost>::set_item_count(&mut this.get_popup().unwrap(), count);
+    }
+    fn set_item_text(this: &mut Gd<Self>, idx: i32, text: &str) {
+        <PopupMenu as ItemHost>::set_item_text(&mut this.get_popup().unwrap(), idx, text);
+    }
+    fn set_item_icon(this: &mut Gd<Self>, idx: i32, icon: Option<&Gd<Texture2D>>) {
+        <PopupMenu as ItemHost>::set_item_icon(&mut this.get_popup().unwrap(), idx, icon);
+    }
+    fn set_item_disabled(this: &mut Gd<Self>, idx: i32, disabled: bool) {
+        <PopupMenu as ItemHost>::set_item_disabled(&mut this.get_popup().unwrap(), idx, disabled);
+    }
+    fn set_item_checked(this: &mut Gd<Self>, idx: i32, checked: Option<bool>) {
+        <PopupMenu as ItemHost>::set_item_checked(&mut this.get_popup().unwrap(), idx, checked);
+    }
+    fn set_item_id(this: &mut Gd<Self>, idx: i32, id: i32) {
+        <PopupMenu as ItemHost>::set_item_id(&mut this.get_popup().unwrap(), idx, id);
+    }
+    fn set_item_submenu(this: &mut Gd<Self>, idx: i32, submenu: Option<&Gd<PopupMenu>>) {
+        <PopupMenu as ItemHost>::set_item_submenu(&mut this.get_popup().unwrap(), idx, submenu);
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct ItemProps {
+    text: String,
+    icon: Option<Gd<Texture2D>>,
+    disabled: bool,
+    checked: Option<bool>,
+    id: Option<i32>,
+    pub(crate) submenu: Option<Gd<PopupMenu>>,
+}
+
+impl ItemProps {
+    pub(crate) fn new(text: String) -> Self {
+        Self {
+            text,
+            icon: None,
+            disabled: false,
+            checked: None,
+            id: None,
+            submenu: None,
+        }
+    }
+    pub(crate) fn icon(&mut self, icon: Gd<Texture2D>) {
+        self.icon = Some(icon);
+    }
+    pub(crate) fn disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    fn apply<N: ItemHost>(
+        &self,
+        prev: Option<&Self>,
+        node: &mut Gd<N>,
+        idx: i32,
+        ctx: &mut Context,
+    ) {
+        if prev.is_none_or(|prev| prev.text != self.text) {
+            N::set_item_text(node, idx, &self.text);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.icon != self.icon) {
+            N::set_item_icon(node, idx, self.icon.as_ref());
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.disabled != self.disabled) {
+            N::set_item_disabled(node, idx, self.disabled);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.checked != self.checked) {
+            N::set_item_checked(node, idx, self.checked);
+            ctx.stats.attrs_written += 1;
+        }
+        // items without an id just use their index
+        if prev.is_none_or(|prev| prev.id != self.id) {
+            N::set_item_id(node, idx, self.id.unwrap_or(idx));
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.submenu != self.submenu) {
+            N::set_item_submenu(node, idx, self.submenu.as_ref());
+            ctx.stats.attrs_written += 1;
+        }
+    }
+}
+
+/// An item's props and its path below the [`ListItems`].
+type ReportedItem = (ItemProps, Vec<ViewID>);
+
+/// Where [`ListItem`]s report themselves in declaration order, so picks can be routed back to them.
+pub(crate) struct ItemCursor {
+    depth: usize,
+    items: Vec<ReportedItem>,
+}
+
+impl Context {
+    fn with_item_cursor<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> (R, Vec<ReportedItem>) {
+        let outer = self.item_cursor.replace(ItemCursor {
+            depth: self.path.len(),
+            items: vec![],
+        });
+        let out = f(self);
+        let cursor = std::mem::replace(&mut self.item_cursor, outer).unwrap();
+        (out, cursor.items)
+    }
+}
+
+/// The items of one [`ItemHost`] along with the views declaring them. Shared by
+/// [`ListItems`] and the menus, which only differ in where the host comes from.
+pub(crate) struct ItemSet<ChildViewState> {
+    // structural views like `Vec` add anchor nodes, which shouldn't end up inside the host
+    anchor: Gd<Node>,
+    id: ViewID,
+    callable: Callable,
+    items: Vec<ReportedItem>,
+    child_id: ViewID,
+    child_view_state: ChildViewState,
+}
+
+/// Writes `items` over what `prev` left at each index. The host only knows indices,
+/// so the selection is carried over by the items' paths in case they moved.
+fn write_items<N: ItemHost>(
+    node: &mut Gd<N>,
+    prev: &[ReportedItem],
+    items: &[ReportedItem],
+    ctx: &mut Context,
+) {
+    let moved = !prev
+        .iter()
+        .map(|(_, path)| path)
+        .eq(items.iter().map(|(_, path)| path));
+    let selected = if moved {
+        N::selected_items(node)
+            .into_iter()
+            .filter_map(|idx| prev.get(idx as usize).map(|(_, path)| path))
+            .filter_map(|path| items.iter().position(|(_, item_path)| item_path == path))
+            .map(|idx| idx as i32)
+            .collect()
+    } else {
+        vec![]
+    };
+
+    if prev.len() != items.len() {
+        N::set_item_count(node, items.len() as i32);
+        ctx.stats.attrs_written += 1;
+    }
+    for (idx, (props, _)) in items.iter().enumerate() {
+        props.apply(prev.get(idx).map(|(prev, _)| prev), node, idx as i32, ctx);
+    }
+
+    if moved && N::selected_items(node) != selected {
+        N::select_items(node, &selected);
+        ctx.stats.attrs_written += 1;
+    }
+}
+
+impl<ChildViewState> ItemSet<ChildViewState> {
+    pub(crate) fn build<N: ItemHost, State: ArgTuple, Children>(
+        children: &Children,
+        node: &mut Gd<N>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) -> Self
+    where
+        Children: View<State, ViewState = ChildViewState>,
+    {
+        let mut anchor = Node::new_alloc();
+        ctx.stats.nodes_created += 1;
+        let child_id = ctx.new_structural_id();
+        let (child_view_state, items) = ctx.with_id(child_id, |ctx| {
+            ctx.with_item_cursor(|ctx| {
+                children.build(ctx, &mut anchor, AnchorType::ChildOf, app_state)
+            })
+        });
+        write_items(node, &[], &items, ctx);
+
+        let id = ctx.new_structural_id();
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        let callable = Callable::from_fn("item_picked", move |args| {
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "item_picked".into(),
+                    args: args.iter().map(|v| (**v).clone()).collect(),
+                },
+                path: path.clone(),
+            });
+        });
+        let (mut source, signal) = N::item_signal(node);
+        source.connect(signal, &callable);
+        ctx.stats.signals_connected += 1;
+
+        Self {
+            anchor,
+            id,
+            callable,
+            items,
+            child_id,
+            child_view_state,
+        }
+    }
+
+    pub(crate) fn rebuild<N: ItemHost, State: ArgTuple, Children>(
+        &mut self,
+        children: &Children,
+        prev: &Children,
+        node: &mut Gd<N>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) where
+        Children: View<State, ViewState = ChildViewState>,
+    {
+        let anchor = &mut self.anchor;
+        let ((), items) = ctx.with_id(self.child_id, |ctx| {
+            ctx.with_item_cursor(|ctx| {
+                children.rebuild(
+                    prev,
+                    &mut self.child_view_state,
+                    ctx,
+                    anchor,
+                    AnchorType::ChildOf,
+                    app_state,
+                );
+            })
+        });
+        write_items(node, &self.items, &items, ctx);
+        self.items = items;
+    }
+
+    pub(crate) fn teardown<N: ItemHost, State: ArgTuple, Children>(
+        &mut self,
+        children: &Children,
+        node: &mut Gd<N>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) where
+        Children: View<State, ViewState = ChildViewState>,
+    {
+        let anchor = &mut self.anchor;
+        ctx.with_id(self.child_id, |ctx| {
+            children.teardown(
+                &mut self.child_view_state,
+                ctx,
+                anchor,
+                AnchorType::ChildOf,
+                app_state,
+            );
+        });
+        self.anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
+        // the node outlives the view, so it must not send messages anymore
+        if ctx.keep_nodes || ctx.pooling() {
+            let (mut source, signal) = N::item_signal(node);
+            source.disconnect(signal, &self.callable);
+            ctx.stats.signals_disconnected += 1;
+        }
+        if ctx.pooling() {
+            N::set_item_count(node, 0);
+            ctx.stats.attrs_written += 1;
+        }
+    }
+
+    /// Hands the message back if it isn't meant for the items.
+    pub(crate) fn message<N: ItemHost, State: ArgTuple, Children>(
+        &mut self,
+        children: &Children,
+        node: &Gd<N>,
+        msg: Message,
+        path: &[ViewID],
+        app_state: &mut State,
+    ) -> Result<MessageResult, Message>
+    where
+        Children: View<State, ViewState = ChildViewState>,
+    {
+        match (msg, path.split_first()) {
+            (Message::Signal { args, .. }, Some((start, []))) if *start == self.id => {
+                let idx = args
+                    .first()
+                    .and_then(|arg| arg.try_to::<i32>().ok())
+                    .map(|arg| N::item_index(node, arg));
+                Ok(match idx.and_then(|idx| self.items.get(idx as usize)) {
+                    Some((_, item_path)) => children.message(
+                        Message::Signal {
+                            name: "selected".into(),
+                            args: Box::new([]),
+                        },
+                        item_path,
+                        &mut self.child_view_state,
+                        app_state,
+                    ),
+                    None => MessageResult::Success,
+                })
+            }
+            (msg, Some((start, rest))) if *start == self.child_id => {
+                Ok(children.message(msg, rest, &mut self.child_view_state, app_state))
+            }
+            (msg, _) => Err(msg),
+        }
+    }
+
+    pub(crate) fn inspect<State: ArgTuple, Children>(
+        &self,
+        children: &Children,
+        name: &str,
+        out: &mut Inspector,
+    ) where
+        Children: View<State, ViewState = ChildViewState>,
+    {
+        out.view(name, &[], |out| {
+            out.with_id(self.child_id, |out| {
+                children.inspect(&self.child_view_state, out);
+            });
+        });
+    }
+}
+
+pub(crate) fn report_item(props: ItemProps, ctx: &mut Context) {
+    let cursor = ctx
+        .item_cursor
+        .as_mut()
+        .expect("items have to be inside `list_items` or a menu");
+    cursor
+        .items
+        .push((props, ctx.path[cursor.depth..].to_vec()));
+}
+
+/// Builds `children` as the items of an [`ItemHost`], picking an item calls its callback.
+/// The host's selection follows the items when a keyed `Vec` of them reorders.
+pub struct ListItems<N, Children, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) children: Children,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct ListItemsViewState<ChildViewState, InnerViewState> {
+    items: ItemSet<ChildViewState>,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Children, Inner> View<State> for ListItems<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: ItemHost,
+{
+    type ViewState = ListItemsViewState<Children::ViewState, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut node = self.inner.get_node(&inner_view_state);
+        ListItemsViewState {
+            items: ItemSet::build(&self.children, &mut node, ctx, app_state),
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        let mut node = self.get_node(state);
+        state
+            .items
+            .rebuild(&self.children, &prev.children, &mut node, ctx, app_state);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let mut node = self.get_node(state);
+        state
+            .items
+            .teardown(&self.children, &mut node, ctx, app_state);
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        let node = self.get_node(view_state);
+        match view_state
+            .items
+            .message(&self.children, &node, msg, path, app_state)
+        {
+            Ok(result) => result,
+            Err(msg) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        state.items.inspect(&self.children, "ListItems", out);
+    }
+}
+
+impl<N, State: ArgTuple, Children, Inner> ElementView<N, State> for ListItems<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: ItemHost,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Children, Inner> ListItems<N, Children, Inner> {
+    impl_element_view! { N }
+}
+
+type ItemCb<State> = Box<dyn Fn(&mut State)>;
+
+/// One item of an [`ItemHost`]. Has to be inside its `list_items`, or a menu.
+pub struct ListItem<State> {
+    props: ItemProps,
+    on_selected: Option<ItemCb<State>>,
+}
+
+impl<State> ListItem<State> {
+    pub fn icon(mut self, icon: Gd<Texture2D>) -> Self {
+        self.props.icon(icon);
+        self
+    }
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.props.disabled(disabled);
+        self
+    }
+    /// Makes the item checkable, only shows up in menus.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.props.checked = Some(checked);
+        self
+    }
+    /// Defaults to the item's index, has to be unique within the list.
+    pub fn id(mut self, id: i32) -> Self {
+        self.props.id = Some(id);
+        self
+    }
+    /// Selected in an `ItemList` or `OptionButton`, pressed in a menu.
+    pub fn on_selected(mut self, cb: impl Fn(&mut State) + 'static) -> Self {
+        self.on_selected = Some(Box::new(cb));
+        self
+    }
+}
+
+impl<State: ArgTuple> View<State> for ListItem<State> {
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "ListItem");
+        report_item(self.props.clone(), ctx);
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        _state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "ListItem");
+        report_item(self.props.clone(), ctx);
+    }
+
+    fn teardown(
+        &self,
+        _state: &mut Self::ViewState,
+        _ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        _view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path) {
+            (Message::Signal { name, .. }, []) if &*name == "selected" => {
+                if let Some(cb) = &self.on_selected {
+                    cb(app_state);
+                }
+                MessageResult::Success
+            }
+            (msg, _) => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, _state: &Self::ViewState, _nodes: &mut Vec<Gd<Node>>) {}
+
+    fn inspect(&self, _state: &Self::ViewState, out: &mut Inspector) {
+        out.view("ListItem", &[], |_| {});
+        out.attr("text", &self.props.text);
+        if let Some(checked) = self.props.checked {
+            out.attr("checked", checked.to_string());
+        }
+        if let Some(id) = self.props.id {
+            out.attr("id", id.to_string());
+        }
+    }
+}
+
+/// An item showing `text`, see [`ListItem`] for the rest.
+pub fn list_item<State: ArgTuple>(text: impl Into<String>) -> ListItem<State> {
+    ListItem {
+        props: ItemProps::new(text.into()),
+        on_selected: None,
+    }
+}
@@ -5,7 +5,8 @@ use godot::{
 use std::{collections::HashMap, hash::Hash};
 
 use crate::{
-    AnchorType, Context, Message, MessageResult, View, ViewID, util::hash, view::ArgTuple,
+    AnchorType, MessageResult, View, ViewID, inspect::Inspector, trace::trace_span, util::hash,
+    view::ArgTuple,
 };
 
 pub struct VecViewState<InnerViewState> {
@@ -27,8 +28,10 @@ where
         anchor_type: super::AnchorType,
         app_state: &mut State,
     ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "Vec");
         let mut vec_anchor = Node::new_alloc();
         anchor_type.add(anchor, &vec_anchor);
+        ctx.stats.nodes_created += 1;
         VecViewState {
             anchor: vec_anchor.clone(),
             inner: self
@@ -47,10 +50,11 @@ where
         prev: &Self,
         state: &mut Self::ViewState,
         ctx: &mut crate::Context,
-        anchor: &mut Node,
-        anchor_type: super::AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: super::AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "rebuild", view = "Vec");
         assert_eq!(
             prev.len(),
             state.inner.len(),
@@ -58,8 +62,6 @@ where
         );
         let mut vec_anchor = state.anchor.clone();
 
-        let mut total_nodes = 0;
-
         let mut prev_map = state
             .inner
             .drain(..)
@@ -67,16 +69,24 @@ where
             .map(|(idx, inner)| {
                 let mut nodes = vec![];
                 prev[idx].1.collect_nodes(&inner, &mut nodes);
-                total_nodes += nodes.len();
                 (&prev[idx].0, (inner, nodes, &prev[idx].1))
             })
             .collect::<HashMap<_, _>>();
 
-        let mut move_idx = vec_anchor.get_index() as usize - total_nodes;
+        // nodes lingering for an exit transition can sit in between, so start at the first
+        // node we own instead of counting back from the anchor
+        let mut move_idx = prev_map
+            .values()
+            .flat_map(|(_, nodes, _)| nodes.iter().map(|node| node.get_index()))
+            .min()
+            .unwrap_or(vec_anchor.get_index()) as usize;
         for (k, v) in self {
             if let Some((mut inner, nodes, prev)) = prev_map.remove(k) {
                 for node in &nodes {
-                    node.get_parent().unwrap().move_child(node, move_idx as i32);
+                    if node.get_index() as usize != move_idx {
+                        node.get_parent().unwrap().move_child(node, move_idx as i32);
+                        ctx.stats.nodes_moved += 1;
+                    }
                     move_idx += 1;
                 }
                 ctx.with_id(ViewID::Key(hash(k)), |ctx| {
@@ -97,7 +107,10 @@ where
                 let mut nodes = vec![];
                 v.collect_nodes(&inner, &mut nodes);
                 for node in &nodes {
-                    node.get_parent().unwrap().move_child(node, move_idx as i32);
+                    if node.get_index() as usize != move_idx {
+                        node.get_parent().unwrap().move_child(node, move_idx as i32);
+                        ctx.stats.nodes_moved += 1;
+                    }
                     move_idx += 1;
                 }
                 state.inner.push(inner);
@@ -124,6 +137,7 @@ where
         anchor_type: super::AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "teardown", view = "Vec");
         assert_eq!(
             self.len(),
             state.inner.len(),
@@ -138,6 +152,7 @@ where
         }
         anchor_type.remove(anchor, &vec_anchor);
         vec_anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
     }
 
     fn message(
@@ -178,4 +193,12 @@ where
         }
         nodes.push(state.anchor.clone());
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("Vec", std::slice::from_ref(&state.anchor), |out| {
+            for ((k, inner), state) in self.iter().zip(&state.inner) {
+                out.with_id(ViewID::Key(hash(k)), |out| inner.inspect(state, out));
+            }
+        });
+    }
 }
@@ -0,0 +1,261 @@
+use std::marker::PhantomData;
+
+use godot::{
+    classes::{CanvasItem, Node, node::ProcessMode},
+    obj::{Gd, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, Message, MessageResult, View, ViewID, inspect::Inspector,
+    trace::trace_span,
+};
+
+/// The top-level nodes of a hidden subtree, with the process mode and visibility they had before.
+pub(crate) struct Inactive(Vec<(Gd<Node>, ProcessMode, Option<bool>)>);
+
+/// Hides a built subtree and pauses it, so hidden listeners and timers stay quiet too.
+pub(crate) fn deactivate(nodes: Vec<Gd<Node>>) -> Inactive {
+    Inactive(
+        nodes
+            .into_iter()
+            .map(|mut node| {
+                let mode = node.get_process_mode();
+                node.set_process_mode(ProcessMode::DISABLED);
+                let visible = node.clone().try_cast::<CanvasItem>().ok().map(|mut item| {
+                    let visible = item.is_visible();
+                    item.set_visible(false);
+                    visible
+                });
+                (node, mode, visible)
+            })
+            .collect(),
+    )
+}
+
+impl Inactive {
+    /// Puts the nodes back the way they were, so ones hidden or paused on purpose stay that way.
+    pub(crate) fn reactivate(self) {
+        for (mut node, mode, visible) in self.0 {
+            if !node.is_instance_valid() {
+                continue;
+            }
+            node.set_process_mode(mode);
+            if let Some(visible) = visible
+                && let Ok(mut item) = node.try_cast::<CanvasItem>()
+            {
+                item.set_visible(visible);
+            }
+        }
+    }
+}
+
+/// Shows the view for `key`, but keeps the views for the keys shown before built and hidden,
+/// so switching back gets them as they were left, scroll position and all.
+/// Nothing gets evicted, every key ever shown stays built until the whole view is torn down.
+pub struct KeepAlive<State, K, InnerFn> {
+    key: K,
+    inner_fn: InnerFn,
+    _p: PhantomData<State>,
+}
+
+struct Branch<K, V, VS> {
+    key: K,
+    id: ViewID,
+    view: V,
+    view_state: VS,
+    inactive: Option<Inactive>,
+}
+
+pub struct KeepAliveViewState<K, V, VS> {
+    anchor: Gd<Node>,
+    branches: Vec<Branch<K, V, VS>>,
+}
+
+impl<State: ArgTuple, K, InnerFn, Inner> View<State> for KeepAlive<State, K, InnerFn>
+where
+    K: PartialEq + Clone,
+    InnerFn: Fn(&mut State) -> Inner,
+    Inner: View<State>,
+{
+    type ViewState = KeepAliveViewState<K, Inner, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "KeepAlive");
+        let mut keep_anchor = Node::new_alloc();
+        anchor_type.add(anchor, &keep_anchor);
+        ctx.stats.nodes_created += 1;
+
+        let id = ctx.new_structural_id();
+        let view = (self.inner_fn)(app_state);
+        let view_state = ctx.with_id(id, |ctx| {
+            view.build(ctx, &mut keep_anchor, AnchorType::Before, app_state)
+        });
+        KeepAliveViewState {
+            anchor: keep_anchor,
+            branches: vec![Branch {
+                key: self.key.clone(),
+                id,
+                view,
+                view_state,
+                inactive: None,
+            }],
+        }
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "KeepAlive");
+        for branch in &mut state.branches {
+            if branch.inactive.is_none() && branch.key != self.key {
+                let mut nodes = vec![];
+                branch.view.collect_nodes(&branch.view_state, &mut nodes);
+                branch.inactive = Some(deactivate(nodes));
+            }
+        }
+
+        let view = (self.inner_fn)(app_state);
+        let keep_anchor = &mut state.anchor;
+        match state
+            .branches
+            .iter_mut()
+            .find(|branch| branch.key == self.key)
+        {
+            Some(branch) => {
+                // before rebuilding, so attrs changing the visibility meanwhile win
+                if let Some(inactive) = branch.inactive.take() {
+                    inactive.reactivate();
+                }
+                ctx.with_id(branch.id, |ctx| {
+                    view.rebuild(
+                        &branch.view,
+                        &mut branch.view_state,
+                        ctx,
+                        keep_anchor,
+                        AnchorType::Before,
+                        app_state,
+                    );
+                });
+                branch.view = view;
+            }
+            None => {
+                let id = ctx.new_structural_id();
+                let view_state = ctx.with_id(id, |ctx| {
+                    view.build(ctx, keep_anchor, AnchorType::Before, app_state)
+                });
+                state.branches.push(Branch {
+                    key: self.key.clone(),
+                    id,
+                    view,
+                    view_state,
+                    inactive: None,
+                });
+            }
+        }
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "KeepAlive");
+        let keep_anchor = &mut state.anchor;
+        for mut branch in state.branches.drain(..) {
+            // pooled nodes have to go back the way they were, not hidden and paused
+            if let Some(inactive) = branch.inactive.take() {
+                inactive.reactivate();
+            }
+            ctx.with_id(branch.id, |ctx| {
+                branch.view.teardown(
+                    &mut branch.view_state,
+                    ctx,
+                    keep_anchor,
+                    AnchorType::Before,
+                    app_state,
+                );
+            });
+        }
+        anchor_type.remove(anchor, &state.anchor);
+        state.anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match path.split_first().and_then(|(start, rest)| {
+            view_state
+                .branches
+                .iter_mut()
+                .find(|branch| branch.id == *start)
+                .map(|branch| (branch, rest))
+        }) {
+            Some((branch, rest)) => {
+                branch
+                    .view
+                    .message(msg, rest, &mut branch.view_state, app_state)
+            }
+            None => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        for branch in &state.branches {
+            branch.view.collect_nodes(&branch.view_state, nodes);
+        }
+        nodes.push(state.anchor.clone());
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("KeepAlive", std::slice::from_ref(&state.anchor), |out| {
+            for branch in &state.branches {
+                out.with_id(branch.id, |out| {
+                    branch.view.inspect(&branch.view_state, out);
+                });
+            }
+        });
+        out.attr("branches", state.branches.len().to_string());
+    }
+}
+
+/// Shows `inner_fn(state)` under `key`, keeping what was built for earlier keys hidden.
+/// Each key is only rebuilt against its own previous view, so returning an
+/// [`AnyView`](crate::AnyView) lets the keys show entirely different views.
+///
+/// Every key stays built until this view goes away, so it's meant for a handful of tabs or pages,
+/// not for keys that keep changing like ids of loaded items.
+pub fn keep_alive<State: ArgTuple, K, InnerFn, Inner>(
+    key: K,
+    inner_fn: InnerFn,
+) -> KeepAlive<State, K, InnerFn>
+where
+    K: PartialEq + Clone,
+    InnerFn: Fn(&mut State) -> Inner,
+    Inner: View<State>,
+{
+    KeepAlive {
+        key,
+        inner_fn,
+        _p: PhantomData,
+    }
+}
@@ -1,9 +1,6 @@
 use std::marker::PhantomData;
 
-use gdx_macro::impl_arg_tuple;
-use replace_with::replace_with_or_abort;
-
-use crate::{View, view::ArgTuple};
+use crate::{View, inspect::Inspector, view::ArgTuple};
 
 pub struct MapState<Inner, MapFn, ChildState> {
     inner: Inner,
@@ -78,6 +75,10 @@ where
     ) {
         self.inner.collect_nodes(state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(state, out);
+    }
 }
 
 pub fn map<ParentState: ArgTuple, ChildState: ArgTuple, MapFn, Inner>(
@@ -0,0 +1,313 @@
+use std::marker::PhantomData;
+
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{
+        Control, InputEvent, InputEventMouseButton, Node, PopupMenu, Texture2D, node::InternalMode,
+    },
+    global::MouseButton,
+    meta::ToGodot,
+    obj::{Gd, Inherits, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, ElementView, Message, MessageResult, View, ViewID,
+    inspect::Inspector,
+    trace::trace_span,
+    view::{
+        element::impl_element_view,
+        items::{ItemProps, ItemSet, report_item},
+    },
+};
+
+/// Opens a `PopupMenu` holding `children` when the element gets right clicked.
+/// The items are [`list_item`](crate::list_item)s and [`submenu`]s, the popup is owned by the modifier.
+pub struct ContextMenu<N, Children, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) children: Children,
+    pub(crate) _p: PhantomData<N>,
+}
+
+pub struct ContextMenuViewState<ChildViewState, InnerViewState> {
+    popup: Gd<PopupMenu>,
+    callable: Callable,
+    items: ItemSet<ChildViewState>,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Children, Inner> View<State> for ContextMenu<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: Inherits<Node> + Inherits<Control>,
+{
+    type ViewState = ContextMenuViewState<Children::ViewState, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let mut control = self.inner.get_node(&inner_view_state).upcast::<Control>();
+
+        // internal, so it doesn't get in the way of the element's own children
+        let mut popup = PopupMenu::new_alloc();
+        control
+            .add_child_ex(&popup)
+            .internal(InternalMode::BACK)
+            .done();
+        ctx.stats.nodes_created += 1;
+
+        let items = ItemSet::build(&self.children, &mut popup, ctx, app_state);
+
+        // opening doesn't need the state, so it happens right away instead of going through a message
+        let (source, mut menu) = (control.clone(), popup.clone());
+        let callable = Callable::from_fn("context_menu", move |args| {
+            let Some(event) = args
+                .first()
+                .and_then(|v| v.try_to::<Gd<InputEvent>>().ok())
+                .and_then(|v| v.try_cast::<InputEventMouseButton>().ok())
+            else {
+                return;
+            };
+            if !event.is_pressed() || event.get_button_index() != MouseButton::RIGHT {
+                return;
+            }
+            let position = source.get_screen_transform() * event.get_position();
+            menu.set_position(position.cast_int());
+            menu.popup();
+            source.clone().accept_event();
+        });
+        control.connect("gui_input", &callable);
+        ctx.stats.signals_connected += 1;
+
+        ContextMenuViewState {
+            popup,
+            callable,
+            items,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        state.items.rebuild(
+            &self.children,
+            &prev.children,
+            &mut state.popup,
+            ctx,
+            app_state,
+        );
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        state
+            .items
+            .teardown(&self.children, &mut state.popup, ctx, app_state);
+        if ctx.keep_nodes || ctx.pooling() {
+            let mut control = self.get_node(state).upcast::<Control>();
+            control.disconnect("gui_input", &state.callable);
+            ctx.stats.signals_disconnected += 1;
+        }
+        // lingering nodes keep their menu until they go away themselves
+        if !ctx.keep_nodes {
+            state.popup.queue_free();
+            ctx.stats.nodes_freed += 1;
+        }
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        let popup = view_state.popup.clone();
+        match view_state
+            .items
+            .message(&self.children, &popup, msg, path, app_state)
+        {
+            Ok(result) => result,
+            Err(msg) => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.signal("gui_input");
+        state.items.inspect(&self.children, "ContextMenu", out);
+    }
+}
+
+impl<N, State: ArgTuple, Children, Inner> ElementView<N, State> for ContextMenu<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: Inherits<Node> + Inherits<Control>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Children, Inner> ContextMenu<N, Children, Inner> {
+    impl_element_view! { N }
+}
+
+/// A menu item opening another menu with `children` in it.
+pub struct SubMenu<Children> {
+    props: ItemProps,
+    children: Children,
+}
+
+impl<Children> SubMenu<Children> {
+    pub fn icon(mut self, icon: Gd<Texture2D>) -> Self {
+        self.props.icon(icon);
+        self
+    }
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.props.disabled(disabled);
+        self
+    }
+}
+
+pub struct SubMenuViewState<ChildViewState> {
+    popup: Gd<PopupMenu>,
+    items: ItemSet<ChildViewState>,
+}
+
+impl<State: ArgTuple, Children> View<State> for SubMenu<Children>
+where
+    Children: View<State>,
+{
+    type ViewState = SubMenuViewState<Children::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "SubMenu");
+        // the parent menu adopts it once it's set as the item's submenu
+        let mut popup = PopupMenu::new_alloc();
+        ctx.stats.nodes_created += 1;
+        let items = ItemSet::build(&self.children, &mut popup, ctx, app_state);
+
+        let mut props = self.props.clone();
+        props.submenu = Some(popup.clone());
+        report_item(props, ctx);
+
+        SubMenuViewState { popup, items }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "SubMenu");
+        state.items.rebuild(
+            &self.children,
+            &prev.children,
+            &mut state.popup,
+            ctx,
+            app_state,
+        );
+
+        let mut props = self.props.clone();
+        props.submenu = Some(state.popup.clone());
+        report_item(props, ctx);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "SubMenu");
+        state
+            .items
+            .teardown(&self.children, &mut state.popup, ctx, app_state);
+        // the parent menu owns it now, a lingering one takes it along when it goes
+        if !ctx.keep_nodes {
+            state.popup.queue_free();
+            ctx.stats.nodes_freed += 1;
+        }
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        let popup = view_state.popup.clone();
+        view_state
+            .items
+            .message(&self.children, &popup, msg, path, app_state)
+            .unwrap_or_else(MessageResult::Stale)
+    }
+
+    fn collect_nodes(&self, _state: &Self::ViewState, _nodes: &mut Vec<Gd<Node>>) {}
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        state.items.inspect(&self.children, "SubMenu", out);
+    }
+}
+
+/// A menu item showing `text` that opens `children` as a nested menu.
+pub fn submenu<Children>(text: impl Into<String>, children: Children) -> SubMenu<Children> {
+    SubMenu {
+        props: ItemProps::new(text.into()),
+        children,
+    }
+}
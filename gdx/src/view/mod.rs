@@ -1,25 +1,32 @@
 pub mod any;
 pub mod either;
 pub mod element;
+pub mod history;
+pub mod input;
+pub mod items;
 pub mod iter;
+pub mod keep_alive;
 pub mod map;
+pub mod menu;
 pub mod option;
 pub mod proxy;
+pub mod router;
+pub mod task;
+pub mod timer;
+pub mod tree;
 pub mod using;
+pub mod virtual_list;
 
 use replace_with::replace_with_or_abort;
 use std::ops::Deref;
-use std::{collections::HashMap, hash::Hash};
 
 use gdx_macro::impl_arg_tuple;
-use godot::{
-    classes::Node,
-    meta::AsArg,
-    obj::{Gd, NewAlloc},
-};
+use godot::{classes::Node, obj::Gd};
 
-use crate::ctx::{Context, Message, MessageResult};
-use crate::util::hash;
+use crate::{
+    ctx::{Context, Message, MessageResult},
+    inspect::{Inspector, short_type_name},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ViewID {
@@ -98,6 +105,14 @@ pub trait View<State: ArgTuple> {
     ) -> MessageResult;
 
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>);
+
+    /// Describes the view for [`ViewTreeDump`](crate::ViewTreeDump)s. The default lists it as a
+    /// leaf under its type name, owning whatever [`View::collect_nodes`] returns.
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        let mut nodes = vec![];
+        self.collect_nodes(state, &mut nodes);
+        out.view(short_type_name::<Self>(), &nodes, |_| {});
+    }
 }
 
 impl<State: ArgTuple, Inner> View<State> for Box<Inner>
@@ -154,6 +169,10 @@ where
     fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
         self.deref().collect_nodes(state, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.deref().inspect(state, out);
+    }
 }
 
 macro_rules! tuple_impl {
@@ -227,6 +246,15 @@ macro_rules! tuple_impl {
                         self.$v.collect_nodes(&state.$v.0, nodes);
                     )*
                 }
+
+                #[allow(unused_variables)]
+                fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+                    $(
+                        out.with_id(state.$v.1, |out| {
+                            self.$v.inspect(&state.$v.0, out);
+                        });
+                    )*
+                }
             }
         }
     };
@@ -3,7 +3,10 @@ use godot::{
     obj::{Gd, NewAlloc},
 };
 
-use crate::{AnchorType, Context, Message, MessageResult, View, ViewID, view::ArgTuple};
+use crate::{
+    AnchorType, Context, Message, MessageResult, View, ViewID, inspect::Inspector,
+    trace::trace_span, view::ArgTuple,
+};
 
 pub struct OptionViewState<InnerViewState> {
     anchor: Gd<Node>,
@@ -23,8 +26,10 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "Option");
         let mut opt_anchor = Node::new_alloc();
         anchor_type.add(anchor, &opt_anchor);
+        ctx.stats.nodes_created += 1;
         OptionViewState {
             anchor: opt_anchor.clone(),
             inner: self.as_ref().map(|inner| {
@@ -44,10 +49,11 @@ where
         prev: &Self,
         state: &mut Self::ViewState,
         ctx: &mut Context,
-        anchor: &mut Node,
-        anchor_type: AnchorType,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "rebuild", view = "Option");
         assert_eq!(
             prev.is_some(),
             state.inner.is_some(),
@@ -100,6 +106,7 @@ where
         anchor_type: AnchorType,
         app_state: &mut State,
     ) {
+        trace_span!(trace_span, "teardown", view = "Option");
         assert_eq!(
             self.is_some(),
             state.inner.is_some(),
@@ -114,6 +121,7 @@ where
         }
         anchor_type.remove(anchor, &opt_anchor);
         opt_anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
     }
 
     fn message(
@@ -155,4 +163,12 @@ where
         }
         nodes.push(state.anchor.clone());
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("Option", std::slice::from_ref(&state.anchor), |out| {
+            if let Some((val, (inner, id))) = self.as_ref().zip(state.inner.as_ref()) {
+                out.with_id(*id, |out| val.inspect(inner, out));
+            }
+        });
+    }
 }
@@ -2,11 +2,13 @@ use std::{marker::PhantomData, sync::Arc};
 
 use crate::{
     ArgTuple, Message, MessageResult, View, ViewID,
-    ctx::{FullMessage, MsgQueue},
+    ctx::{FullMessage, MsgQueue, SendMessage, SendMsgQueue},
+    inspect::Inspector,
 };
 
 pub struct MessageProxy<T> {
     queue: MsgQueue,
+    send_queue: SendMsgQueue,
     path: Arc<[ViewID]>,
     _p: PhantomData<T>,
 }
@@ -20,7 +22,41 @@ impl<T: 'static> MessageProxy<T> {
         });
     }
 }
+impl<T: Send + 'static> MessageProxy<T> {
+    /// A proxy that can be moved to worker threads, what it sends arrives in the next [`App::run`](crate::App::run).
+    pub fn to_send(&self) -> SendMessageProxy<T> {
+        SendMessageProxy {
+            queue: self.send_queue.clone(),
+            path: self.path.clone(),
+            _p: PhantomData,
+        }
+    }
+}
 impl<T> Clone for MessageProxy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            send_queue: self.send_queue.clone(),
+            path: self.path.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+pub struct SendMessageProxy<T> {
+    queue: SendMsgQueue,
+    path: Arc<[ViewID]>,
+    _p: PhantomData<fn(T)>,
+}
+impl<T: Send + 'static> SendMessageProxy<T> {
+    pub fn send(&self, value: T) {
+        self.queue.lock().push_back(SendMessage {
+            value: Box::new(value),
+            path: self.path.clone(),
+        });
+    }
+}
+impl<T> Clone for SendMessageProxy<T> {
     fn clone(&self) -> Self {
         Self {
             queue: self.queue.clone(),
@@ -53,6 +89,7 @@ where
     ) -> Self::ViewState {
         let proxy = MessageProxy {
             queue: ctx.msg_queue.clone(),
+            send_queue: ctx.send_queue.clone(),
             path: ctx.path.clone().into(),
             _p: PhantomData,
         };
@@ -72,6 +109,7 @@ where
     ) {
         let proxy = MessageProxy {
             queue: ctx.msg_queue.clone(),
+            send_queue: ctx.send_queue.clone(),
             path: ctx.path.clone().into(),
             _p: PhantomData,
         };
@@ -102,7 +140,7 @@ where
     ) -> crate::MessageResult {
         if path.is_empty() {
             match msg {
-                Message::Proxy { value } => {
+                Message::Proxy { value } if value.is::<T>() => {
                     (self.cb)(app_state, value.downcast().unwrap());
                     return MessageResult::Success;
                 }
@@ -121,6 +159,10 @@ where
     ) {
         state.0.collect_nodes(&state.1, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        state.0.inspect(&state.1, out);
+    }
 }
 
 pub fn proxy<State: ArgTuple, T, InnerFn, Cb, Inner>(
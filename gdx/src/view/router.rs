@@ -0,0 +1,449 @@
+use std::{cell::Cell, marker::PhantomData, rc::Rc, sync::Arc};
+
+use godot::{
+    builtin::StringName,
+    classes::Node,
+    meta::ToGodot,
+    obj::{Gd, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, Message, MessageResult, View, ViewID,
+    classes::{GdxInputListener, InputHandler},
+    ctx::FullMessage,
+    inspect::Inspector,
+    trace::trace_span,
+    view::keep_alive::{Inactive, deactivate},
+};
+
+/// The stack of routes a [`router`] shows, kept in the app state so handlers can navigate.
+/// The first route is the root and never gets popped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Navigator<R> {
+    stack: Vec<R>,
+}
+
+impl<R> Navigator<R> {
+    pub fn new(root: R) -> Self {
+        Self { stack: vec![root] }
+    }
+    pub fn push(&mut self, route: R) {
+        self.stack.push(route);
+    }
+    /// Goes back a screen, does nothing on the root.
+    pub fn pop(&mut self) -> Option<R> {
+        if self.can_pop() {
+            self.stack.pop()
+        } else {
+            None
+        }
+    }
+    /// Swaps out the current screen without growing the stack.
+    pub fn replace(&mut self, route: R) {
+        *self.stack.last_mut().unwrap() = route;
+    }
+    /// Drops the whole stack and starts over at `root`.
+    pub fn reset(&mut self, root: R) {
+        self.stack.clear();
+        self.stack.push(root);
+    }
+    pub fn can_pop(&self) -> bool {
+        self.stack.len() > 1
+    }
+    pub fn current(&self) -> &R {
+        self.stack.last().unwrap()
+    }
+    pub fn stack(&self) -> &[R] {
+        &self.stack
+    }
+}
+
+/// Shows the screen for the current route of a [`Navigator`], building it with `render`.
+/// Screens underneath are torn down unless [`keep_alive`](Self::keep_alive) is on, and a
+/// `.transition(..)` on a screen's root element animates it in and out.
+pub struct Router<State, R, Lens, Render> {
+    lens: Lens,
+    render: Render,
+    keep_alive: bool,
+    back_action: Option<StringName>,
+    _p: PhantomData<(State, R)>,
+}
+
+impl<State, R, Lens, Render> Router<State, R, Lens, Render> {
+    /// Keeps the screens underneath the current one built but hidden, so they come back
+    /// exactly as they were left instead of being rebuilt. A screen covered by a push is hidden
+    /// right away without playing its exit transition, only popping it animates it out.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+    /// Pops the navigator when the `InputMap` action, e.g. `ui_cancel`, is pressed and nothing handled it before.
+    pub fn back_action(mut self, action: impl Into<StringName>) -> Self {
+        self.back_action = Some(action.into());
+        self
+    }
+}
+
+struct Screen<R, V, VS> {
+    route: R,
+    id: ViewID,
+    built: Option<(V, VS)>,
+    inactive: Option<Inactive>,
+}
+
+pub struct RouterViewState<R, V, VS> {
+    anchor: Gd<Node>,
+    screens: Vec<Screen<R, V, VS>>,
+    back: Option<(Gd<GdxInputListener>, Rc<Cell<bool>>)>,
+    back_id: ViewID,
+}
+
+impl<State: ArgTuple, R, Lens, Render, V> Router<State, R, Lens, Render>
+where
+    R: Clone + PartialEq,
+    Lens: Fn(&mut State) -> &mut Navigator<R>,
+    Render: Fn(&mut State, &R) -> V,
+    V: View<State>,
+{
+    fn back_handler(
+        &self,
+        ctx: &Context,
+        path: Arc<[ViewID]>,
+        can_pop: Rc<Cell<bool>>,
+    ) -> InputHandler {
+        let action = self.back_action.clone().unwrap();
+        let msgs = ctx.msg_queue.clone();
+        Box::new(move |event| {
+            // leave the action to someone else when there's nowhere to go back to
+            if !can_pop.get() || !event.is_action_pressed(&action) {
+                return false;
+            }
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "back".into(),
+                    args: Box::new([event.to_variant()]),
+                },
+                path: path.clone(),
+            });
+            true
+        })
+    }
+
+    /// Adds, updates or removes the back listener to match `back_action`.
+    fn sync_back(&self, state: &mut RouterViewState<R, V, V::ViewState>, ctx: &mut Context) {
+        let can_pop = state.screens.len() > 1;
+        match (&mut state.back, &self.back_action) {
+            (None, Some(_)) => {
+                let flag = Rc::new(Cell::new(can_pop));
+                let path: Arc<[ViewID]> = ctx.with_id(state.back_id, |ctx| ctx.path.clone().into());
+                let mut listener = GdxInputListener::new_alloc();
+                listener.bind_mut().handler = Some(self.back_handler(ctx, path, flag.clone()));
+                AnchorType::Before.add(&mut state.anchor, &listener.clone().upcast());
+                ctx.stats.nodes_created += 1;
+                state.back = Some((listener, flag));
+            }
+            (Some((listener, flag)), Some(_)) => {
+                flag.set(can_pop);
+                let path: Arc<[ViewID]> = ctx.with_id(state.back_id, |ctx| ctx.path.clone().into());
+                listener.bind_mut().handler = Some(self.back_handler(ctx, path, flag.clone()));
+            }
+            (Some(_), None) => {
+                let (mut listener, _) = state.back.take().unwrap();
+                listener.bind_mut().handler = None;
+                AnchorType::Before.remove(&mut state.anchor, &listener.clone().upcast());
+                listener.queue_free();
+                ctx.stats.nodes_freed += 1;
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Brings the screens in line with the navigator's stack. Only the top one gets
+    /// rendered, the ones below are either hidden or torn down.
+    fn sync_screens(
+        &self,
+        state: &mut RouterViewState<R, V, V::ViewState>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) {
+        let routes = (self.lens)(app_state).stack().to_vec();
+        let anchor = &mut state.anchor;
+        let kept = state
+            .screens
+            .iter()
+            .zip(&routes)
+            .take_while(|(screen, route)| screen.route == **route)
+            .count();
+        while state.screens.len() > kept {
+            let screen = state.screens.pop().unwrap();
+            // a popped screen that was kept alive is still hidden, pooled nodes shouldn't stay that way
+            if let Some(inactive) = screen.inactive {
+                inactive.reactivate();
+            }
+            if let Some((view, mut view_state)) = screen.built {
+                ctx.with_id(screen.id, |ctx| {
+                    view.teardown(&mut view_state, ctx, anchor, AnchorType::Before, app_state);
+                });
+            }
+        }
+        for route in routes.into_iter().skip(kept) {
+            state.screens.push(Screen {
+                route,
+                id: ctx.new_structural_id(),
+                built: None,
+                inactive: None,
+            });
+        }
+
+        let Some((top, below)) = state.screens.split_last_mut() else {
+            return;
+        };
+        for screen in below {
+            match screen.built.take() {
+                Some((view, view_state)) if self.keep_alive => {
+                    if screen.inactive.is_none() {
+                        let mut nodes = vec![];
+                        view.collect_nodes(&view_state, &mut nodes);
+                        screen.inactive = Some(deactivate(nodes));
+                    }
+                    screen.built = Some((view, view_state));
+                }
+                Some((view, mut view_state)) => {
+                    if let Some(inactive) = screen.inactive.take() {
+                        inactive.reactivate();
+                    }
+                    ctx.with_id(screen.id, |ctx| {
+                        view.teardown(&mut view_state, ctx, anchor, AnchorType::Before, app_state);
+                    });
+                }
+                None => {}
+            }
+        }
+
+        let view = (self.render)(app_state, &top.route);
+        match &mut top.built {
+            Some((prev, view_state)) => {
+                if let Some(inactive) = top.inactive.take() {
+                    inactive.reactivate();
+                }
+                ctx.with_id(top.id, |ctx| {
+                    view.rebuild(prev, view_state, ctx, anchor, AnchorType::Before, app_state);
+                });
+                *prev = view;
+            }
+            None => {
+                let view_state = ctx.with_id(top.id, |ctx| {
+                    view.build(ctx, anchor, AnchorType::Before, app_state)
+                });
+                top.built = Some((view, view_state));
+            }
+        }
+    }
+}
+
+impl<State: ArgTuple, R, Lens, Render, V> View<State> for Router<State, R, Lens, Render>
+where
+    R: Clone + PartialEq,
+    Lens: Fn(&mut State) -> &mut Navigator<R>,
+    Render: Fn(&mut State, &R) -> V,
+    V: View<State>,
+{
+    type ViewState = RouterViewState<R, V, V::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "Router");
+        let router_anchor = Node::new_alloc();
+        anchor_type.add(anchor, &router_anchor);
+        ctx.stats.nodes_created += 1;
+        let mut state = RouterViewState {
+            anchor: router_anchor,
+            screens: vec![],
+            back: None,
+            back_id: ctx.new_structural_id(),
+        };
+        self.sync_screens(&mut state, ctx, app_state);
+        self.sync_back(&mut state, ctx);
+        state
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "Router");
+        self.sync_screens(state, ctx, app_state);
+        self.sync_back(state, ctx);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "Router");
+        let router_anchor = &mut state.anchor;
+        for screen in state.screens.drain(..).rev() {
+            if let Some(inactive) = screen.inactive {
+                inactive.reactivate();
+            }
+            if let Some((view, mut view_state)) = screen.built {
+                ctx.with_id(screen.id, |ctx| {
+                    view.teardown(
+                        &mut view_state,
+                        ctx,
+                        router_anchor,
+                        AnchorType::Before,
+                        app_state,
+                    );
+                });
+            }
+        }
+        if let Some((mut listener, _)) = state.back.take() {
+            listener.bind_mut().handler = None;
+            AnchorType::Before.remove(router_anchor, &listener.clone().upcast());
+            listener.queue_free();
+            ctx.stats.nodes_freed += 1;
+        }
+        anchor_type.remove(anchor, &state.anchor);
+        state.anchor.queue_free();
+        ctx.stats.nodes_freed += 1;
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { .. }, Some((start, []))) if *start == view_state.back_id => {
+                (self.lens)(app_state).pop();
+                MessageResult::Success
+            }
+            (msg, Some((start, rest))) => match view_state
+                .screens
+                .iter_mut()
+                .find(|screen| screen.id == *start)
+                .and_then(|screen| screen.built.as_mut())
+            {
+                Some((view, view_state)) => view.message(msg, rest, view_state, app_state),
+                None => MessageResult::Stale(msg),
+            },
+            (msg, None) => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        for (view, view_state) in state
+            .screens
+            .iter()
+            .filter_map(|screen| screen.built.as_ref())
+        {
+            view.collect_nodes(view_state, nodes);
+        }
+        if let Some((listener, _)) = &state.back {
+            nodes.push(listener.clone().upcast());
+        }
+        nodes.push(state.anchor.clone());
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("Router", std::slice::from_ref(&state.anchor), |out| {
+            for screen in &state.screens {
+                if let Some((view, view_state)) = &screen.built {
+                    out.with_id(screen.id, |out| {
+                        view.inspect(view_state, out);
+                    });
+                }
+            }
+        });
+        out.attr("depth", state.screens.len().to_string());
+        if let Some(action) = &self.back_action {
+            out.attr("back_action", action.to_string());
+        }
+    }
+}
+
+/// Shows `render(state, route)` for the current route of the [`Navigator`] that `lens` points at.
+pub fn router<State: ArgTuple, R, Lens, Render, V>(
+    lens: Lens,
+    render: Render,
+) -> Router<State, R, Lens, Render>
+where
+    R: Clone + PartialEq,
+    Lens: Fn(&mut State) -> &mut Navigator<R>,
+    Render: Fn(&mut State, &R) -> V,
+    V: View<State>,
+{
+    Router {
+        lens,
+        render,
+        keep_alive: false,
+        back_action: None,
+        _p: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop() {
+        let mut nav = Navigator::new("home");
+        nav.push("list");
+        nav.push("detail");
+        assert_eq!(nav.current(), &"detail");
+        assert_eq!(nav.stack(), ["home", "list", "detail"]);
+
+        assert_eq!(nav.pop(), Some("detail"));
+        assert_eq!(nav.pop(), Some("list"));
+        assert_eq!(nav.current(), &"home");
+    }
+
+    #[test]
+    fn root_never_pops() {
+        let mut nav = Navigator::new("home");
+        assert!(!nav.can_pop());
+        assert_eq!(nav.pop(), None);
+        assert_eq!(nav.stack(), ["home"]);
+    }
+
+    #[test]
+    fn replace_keeps_depth() {
+        let mut nav = Navigator::new("home");
+        nav.push("list");
+        nav.replace("settings");
+        assert_eq!(nav.stack(), ["home", "settings"]);
+
+        let mut nav = Navigator::new("home");
+        nav.replace("login");
+        assert_eq!(nav.stack(), ["login"]);
+        assert!(!nav.can_pop());
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let mut nav = Navigator::new("home");
+        nav.push("list");
+        nav.push("detail");
+        nav.reset("login");
+        assert_eq!(nav.stack(), ["login"]);
+        assert!(!nav.can_pop());
+    }
+}
@@ -0,0 +1,190 @@
+use std::{future::Future, marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    ArgTuple, Message, MessageResult, View, ViewID,
+    ctx::{FullMessage, MsgQueue},
+    executor::Executor,
+    inspect::Inspector,
+};
+
+/// Spawns futures owned by the surrounding [`Tasks`] view, their outputs get passed to its callback.
+pub struct TaskSpawner<T> {
+    executor: Rc<Executor>,
+    owner: u64,
+    queue: MsgQueue,
+    path: Arc<[ViewID]>,
+    _p: PhantomData<T>,
+}
+impl<T: 'static> TaskSpawner<T> {
+    pub fn spawn(&self, fut: impl Future<Output = T> + 'static) {
+        let queue = self.queue.clone();
+        let path = self.path.clone();
+        self.executor.spawn(self.owner, async move {
+            let value = fut.await;
+            queue.lock().push_back(FullMessage {
+                msg: Message::Proxy {
+                    value: Box::new(value),
+                },
+                path,
+            });
+        });
+    }
+}
+impl<T> Clone for TaskSpawner<T> {
+    fn clone(&self) -> Self {
+        Self {
+            executor: self.executor.clone(),
+            owner: self.owner,
+            queue: self.queue.clone(),
+            path: self.path.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+pub struct Tasks<State, T, InnerFn, Cb> {
+    inner_fn: InnerFn,
+    cb: Cb,
+    _p: PhantomData<(State, T)>,
+}
+
+pub struct TasksViewState<Inner, InnerViewState> {
+    id: ViewID,
+    owner: u64,
+    inner: Inner,
+    inner_view_state: InnerViewState,
+}
+
+impl<State: ArgTuple, T: 'static, InnerFn, Cb> Tasks<State, T, InnerFn, Cb> {
+    fn spawner(&self, ctx: &mut crate::Context, id: ViewID, owner: u64) -> TaskSpawner<T> {
+        TaskSpawner {
+            executor: ctx.executor.clone(),
+            owner,
+            queue: ctx.msg_queue.clone(),
+            path: ctx.with_id(id, |ctx| ctx.path.clone().into()),
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<State: ArgTuple, T: 'static, InnerFn, Cb, Inner> View<State> for Tasks<State, T, InnerFn, Cb>
+where
+    Inner: View<State>,
+    InnerFn: Fn(TaskSpawner<T>) -> Inner,
+    Cb: Fn(&mut State, T),
+{
+    type ViewState = TasksViewState<Inner, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        // its own spot in the path, so outputs don't reach a proxy or tasks view around it
+        let id = ctx.new_structural_id();
+        let owner = ctx.executor.new_owner();
+        let inner = (self.inner_fn)(self.spawner(ctx, id, owner));
+        let inner_view_state = inner.build(ctx, anchor, anchor_type, app_state);
+        TasksViewState {
+            id,
+            owner,
+            inner,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) {
+        let inner = (self.inner_fn)(self.spawner(ctx, state.id, state.owner));
+        inner.rebuild(
+            &state.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        state.inner = inner;
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut crate::Context,
+        anchor: &mut godot::prelude::Node,
+        anchor_type: super::AnchorType,
+        app_state: &mut State,
+    ) {
+        ctx.stats.tasks_cancelled += ctx.executor.cancel(state.owner) as u32;
+        state.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: crate::Message,
+        path: &[super::ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> crate::MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Proxy { value }, Some((start, []))) if *start == view_state.id => {
+                match value.downcast() {
+                    Ok(value) => {
+                        (self.cb)(app_state, *value);
+                        MessageResult::Success
+                    }
+                    Err(value) => MessageResult::Stale(Message::Proxy { value }),
+                }
+            }
+            (msg, _) => {
+                view_state
+                    .inner
+                    .message(msg, path, &mut view_state.inner_view_state, app_state)
+            }
+        }
+    }
+
+    fn collect_nodes(
+        &self,
+        state: &Self::ViewState,
+        nodes: &mut Vec<godot::prelude::Gd<godot::prelude::Node>>,
+    ) {
+        state.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        state.inner.inspect(&state.inner_view_state, out);
+    }
+}
+
+/// Gives the inner view a [`TaskSpawner`], the tasks it spawns get cancelled when this view is torn down.
+pub fn tasks<State: ArgTuple, T, InnerFn, Cb, Inner>(
+    cb: Cb,
+    inner_fn: InnerFn,
+) -> Tasks<State, T, InnerFn, Cb>
+where
+    Inner: View<State>,
+    InnerFn: Fn(TaskSpawner<T>) -> Inner,
+    Cb: Fn(&mut State, T),
+{
+    Tasks {
+        inner_fn,
+        cb,
+        _p: PhantomData,
+    }
+}
@@ -0,0 +1,174 @@
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use godot::{
+    builtin::Callable,
+    classes::{Node, Timer},
+    obj::{Gd, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, Message, MessageResult, View, ViewID, ctx::FullMessage,
+    inspect::Inspector, trace::trace_span,
+};
+
+pub struct TimerView<State, Cb> {
+    wait_time: Duration,
+    one_shot: bool,
+    restart_on_change: bool,
+    cb: Cb,
+    _p: PhantomData<State>,
+}
+
+impl<State, Cb> TimerView<State, Cb> {
+    /// Restarts the countdown when the duration changes, instead of only applying it to the next cycle.
+    pub fn restart_on_change(mut self) -> Self {
+        self.restart_on_change = true;
+        self
+    }
+}
+
+pub struct TimerViewState {
+    timer: Gd<Timer>,
+    callable: Callable,
+}
+
+impl<State: ArgTuple, Cb> View<State> for TimerView<State, Cb>
+where
+    Cb: Fn(&mut State),
+{
+    type ViewState = TimerViewState;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "Timer");
+        let mut timer = Timer::new_alloc();
+        timer.set_wait_time(self.wait_time.as_secs_f64());
+        timer.set_one_shot(self.one_shot);
+        // starts once it's in the tree, which the anchor might not be yet
+        timer.set_autostart(true);
+        anchor_type.add(anchor, &timer.clone().upcast());
+        ctx.stats.nodes_created += 1;
+
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.path.clone().into();
+        let callable = Callable::from_fn("tick", move |_| {
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "timeout".into(),
+                    args: Box::new([]),
+                },
+                path: path.clone(),
+            });
+        });
+        timer.connect("timeout", &callable);
+        ctx.stats.signals_connected += 1;
+
+        TimerViewState { timer, callable }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "Timer");
+        if self.one_shot != prev.one_shot {
+            state.timer.set_one_shot(self.one_shot);
+            ctx.stats.attrs_written += 1;
+        }
+        if self.wait_time != prev.wait_time {
+            // godot only picks the new wait time up on the next start, so the phase is kept
+            state.timer.set_wait_time(self.wait_time.as_secs_f64());
+            ctx.stats.attrs_written += 1;
+            if self.restart_on_change && state.timer.is_inside_tree() {
+                state.timer.start();
+            }
+        }
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        _app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "Timer");
+        state.timer.disconnect("timeout", &state.callable);
+        ctx.stats.signals_disconnected += 1;
+        if state.timer.is_inside_tree() {
+            state.timer.stop();
+        }
+        anchor_type.remove(anchor, &state.timer.clone().upcast());
+        state.timer.queue_free();
+        ctx.stats.nodes_freed += 1;
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        _view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match msg {
+            Message::Signal { ref name, .. } if path.is_empty() && &**name == "timeout" => {
+                (self.cb)(app_state);
+                MessageResult::Success
+            }
+            msg => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        nodes.push(state.timer.clone().upcast());
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view(
+            if self.one_shot { "Timeout" } else { "Interval" },
+            &[state.timer.clone().upcast()],
+            |_| {},
+        );
+        out.attr("wait_time", self.wait_time.as_secs_f64().to_string());
+        out.signal("timeout");
+    }
+}
+
+/// Calls `cb` every `period` for as long as this view is mounted.
+pub fn interval<State: ArgTuple, Cb>(period: Duration, cb: Cb) -> TimerView<State, Cb>
+where
+    Cb: Fn(&mut State),
+{
+    TimerView {
+        wait_time: period,
+        one_shot: false,
+        restart_on_change: false,
+        cb,
+        _p: PhantomData,
+    }
+}
+
+/// Calls `cb` once, `delay` after this view got mounted.
+pub fn timeout<State: ArgTuple, Cb>(delay: Duration, cb: Cb) -> TimerView<State, Cb>
+where
+    Cb: Fn(&mut State),
+{
+    TimerView {
+        wait_time: delay,
+        one_shot: true,
+        restart_on_change: false,
+        cb,
+        _p: PhantomData,
+    }
+}
@@ -0,0 +1,666 @@
+use std::{cell::RefCell, collections::HashMap, marker::PhantomData, rc::Rc, sync::Arc};
+
+use godot::{
+    builtin::{Callable, Variant},
+    classes::{Node, Texture2D, Tree, TreeItem, tree_item::TreeCellMode},
+    meta::ToGodot,
+    obj::{Gd, Inherits, InstanceId},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage, inspect::Inspector, trace::trace_span, view::element::impl_element_view,
+};
+
+/// The view paths of the items with handlers, so the tree's signals reach the right item.
+type ItemRoutes = Rc<RefCell<HashMap<InstanceId, Arc<[ViewID]>>>>;
+
+/// Where [`TreeItemView`]s put their items. `TreeItem`s aren't nodes, so instead of an anchor
+/// node they get the parent item through the context, and report themselves in `order`
+/// so the parent can put its children in the declared order afterwards.
+pub(crate) struct TreeCursor {
+    tree: Gd<Tree>,
+    routes: ItemRoutes,
+    parent: Gd<TreeItem>,
+    order: Vec<Gd<TreeItem>>,
+}
+
+impl Context {
+    fn with_tree_cursor<R>(
+        &mut self,
+        tree: Gd<Tree>,
+        routes: ItemRoutes,
+        parent: Gd<TreeItem>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let outer = self.tree_cursor.replace(TreeCursor {
+            tree,
+            routes,
+            parent,
+            order: vec![],
+        });
+        let out = f(self);
+        let cursor = std::mem::replace(&mut self.tree_cursor, outer).unwrap();
+        reorder(&cursor.parent, &cursor.order, self);
+        out
+    }
+}
+
+fn reorder(parent: &Gd<TreeItem>, order: &[Gd<TreeItem>], ctx: &mut Context) {
+    let mut parent = parent.clone();
+    for (idx, item) in order.iter().enumerate() {
+        if parent.get_child(idx as i32).as_ref() == Some(item) {
+            continue;
+        }
+        let mut item = item.clone();
+        match idx {
+            0 => item.move_before(parent.get_first_child().as_ref()),
+            _ => item.move_after(&order[idx - 1]),
+        }
+        ctx.stats.nodes_moved += 1;
+    }
+}
+
+/// Builds `children` as the items of the `Tree`, under the tree's root.
+pub struct TreeItems<N, Children, Inner> {
+    pub(crate) inner: Inner,
+    pub(crate) children: Children,
+    pub(crate) _p: PhantomData<N>,
+}
+
+const ITEM_SIGNALS: [&str; 3] = ["item_selected", "item_activated", "item_edited"];
+
+pub struct TreeItemsViewState<ChildViewState, InnerViewState> {
+    root: Gd<TreeItem>,
+    owns_root: bool,
+    routes: ItemRoutes,
+    callables: [Callable; 3],
+    child_id: ViewID,
+    child_view_state: ChildViewState,
+    inner_view_state: InnerViewState,
+}
+
+impl<N, State: ArgTuple, Children, Inner> View<State> for TreeItems<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: Inherits<Node> + Inherits<Tree>,
+{
+    type ViewState = TreeItemsViewState<Children::ViewState, Inner::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        let inner_view_state = self.inner.build(ctx, anchor, anchor_type, app_state);
+        let node = self.inner.get_node(&inner_view_state);
+        let mut tree = node.clone().upcast::<Tree>();
+        let (root, owns_root) = match tree.get_root() {
+            Some(root) => (root, false),
+            None => {
+                // a root of our own is just there to hold the items, not a row
+                tree.set_hide_root(true);
+                ctx.stats.attrs_written += 1;
+                (tree.create_item().unwrap(), true)
+            }
+        };
+
+        // the tree emits these for all of its items, one connection routes them by item
+        let routes = ItemRoutes::default();
+        let callables = ITEM_SIGNALS.map(|signal| {
+            let msgs = ctx.msg_queue.clone();
+            let (tree, routes) = (tree.clone(), routes.clone());
+            Callable::from_fn(signal, move |_| {
+                let item = if signal == "item_edited" {
+                    tree.get_edited()
+                } else {
+                    tree.get_selected()
+                };
+                let Some(item) = item else {
+                    return;
+                };
+                let Some(path) = routes.borrow().get(&item.instance_id()).cloned() else {
+                    return;
+                };
+                msgs.lock().push_back(FullMessage {
+                    msg: Message::Signal {
+                        name: signal.into(),
+                        args: Box::new([
+                            item.get_text(0).to_variant(),
+                            item.is_checked(0).to_variant(),
+                        ]),
+                    },
+                    path,
+                });
+            })
+        });
+        for (signal, callable) in ITEM_SIGNALS.iter().zip(&callables) {
+            tree.connect(*signal, callable);
+        }
+        ctx.stats.signals_connected += ITEM_SIGNALS.len() as u32;
+
+        let child_id = ctx.new_structural_id();
+        let child_view_state = ctx.with_tree_cursor(tree, routes.clone(), root.clone(), |ctx| {
+            ctx.with_id(child_id, |ctx| {
+                self.children.build(
+                    ctx,
+                    &mut node.upcast::<Node>(),
+                    AnchorType::ChildOf,
+                    app_state,
+                )
+            })
+        });
+        TreeItemsViewState {
+            root,
+            owns_root,
+            routes,
+            callables,
+            child_id,
+            child_view_state,
+            inner_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        self.inner.rebuild(
+            &prev.inner,
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+        let node = self.get_node(state);
+        let (tree, routes, root) = (
+            node.clone().upcast(),
+            state.routes.clone(),
+            state.root.clone(),
+        );
+        ctx.with_tree_cursor(tree, routes, root, |ctx| {
+            ctx.with_id(state.child_id, |ctx| {
+                self.children.rebuild(
+                    &prev.children,
+                    &mut state.child_view_state,
+                    ctx,
+                    &mut node.upcast::<Node>(),
+                    AnchorType::ChildOf,
+                    app_state,
+                );
+            })
+        });
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        let node = self.get_node(state);
+        let (tree, routes, root) = (
+            node.clone().upcast(),
+            state.routes.clone(),
+            state.root.clone(),
+        );
+        ctx.with_tree_cursor(tree, routes, root, |ctx| {
+            ctx.with_id(state.child_id, |ctx| {
+                self.children.teardown(
+                    &mut state.child_view_state,
+                    ctx,
+                    &mut node.clone().upcast::<Node>(),
+                    AnchorType::ChildOf,
+                    app_state,
+                );
+            })
+        });
+        let mut tree = node.upcast::<Tree>();
+        if ctx.keep_nodes || ctx.pooling() {
+            for (signal, callable) in ITEM_SIGNALS.iter().zip(&state.callables) {
+                tree.disconnect(*signal, callable);
+            }
+            ctx.stats.signals_disconnected += ITEM_SIGNALS.len() as u32;
+        }
+        if state.owns_root {
+            tree.clear();
+            if ctx.pooling() {
+                tree.set_hide_root(false);
+                ctx.stats.attrs_written += 1;
+            }
+        }
+        self.inner.teardown(
+            &mut state.inner_view_state,
+            ctx,
+            anchor,
+            anchor_type,
+            app_state,
+        );
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match path.split_first() {
+            Some((start, rest)) if *start == view_state.child_id => {
+                self.children
+                    .message(msg, rest, &mut view_state.child_view_state, app_state)
+            }
+            _ => self
+                .inner
+                .message(msg, path, &mut view_state.inner_view_state, app_state),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.inner.collect_nodes(&state.inner_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        self.inner.inspect(&state.inner_view_state, out);
+        out.view("TreeItems", &[], |out| {
+            out.with_id(state.child_id, |out| {
+                self.children.inspect(&state.child_view_state, out);
+            });
+        });
+    }
+}
+
+impl<N, State: ArgTuple, Children, Inner> ElementView<N, State> for TreeItems<N, Children, Inner>
+where
+    Inner: ElementView<N, State>,
+    Children: View<State>,
+    N: Inherits<Node> + Inherits<Tree>,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        self.inner.get_node(&state.inner_view_state)
+    }
+}
+
+impl<N, Children, Inner> TreeItems<N, Children, Inner> {
+    impl_element_view! { N }
+}
+
+type ItemCb<State, Arg = ()> = Box<dyn Fn(&mut State, Arg)>;
+
+/// One row of a `Tree`, its children are the nested rows. Has to be inside the `tree_items` of a `Tree`.
+pub struct TreeItemView<State, Children> {
+    text: String,
+    cells: Vec<(i32, String)>,
+    icon: Option<Gd<Texture2D>>,
+    checked: Option<bool>,
+    collapsed: bool,
+    editable: bool,
+    selectable: bool,
+    metadata: Variant,
+    children: Children,
+    on_selected: Option<ItemCb<State>>,
+    on_activated: Option<ItemCb<State>>,
+    on_edited: Option<ItemCb<State, String>>,
+    on_checked: Option<ItemCb<State, bool>>,
+}
+
+impl<State, Children> TreeItemView<State, Children> {
+    pub fn children<NewChildren>(self, children: NewChildren) -> TreeItemView<State, NewChildren> {
+        TreeItemView {
+            text: self.text,
+            cells: self.cells,
+            icon: self.icon,
+            checked: self.checked,
+            collapsed: self.collapsed,
+            editable: self.editable,
+            selectable: self.selectable,
+            metadata: self.metadata,
+            children,
+            on_selected: self.on_selected,
+            on_activated: self.on_activated,
+            on_edited: self.on_edited,
+            on_checked: self.on_checked,
+        }
+    }
+    /// Text for another column, column 0 is the item's own text.
+    pub fn cell(mut self, column: i32, text: impl Into<String>) -> Self {
+        self.cells.push((column, text.into()));
+        self
+    }
+    pub fn icon(mut self, icon: Gd<Texture2D>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+    /// Turns the first column into a checkbox.
+    pub fn checkbox(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+    pub fn metadata(mut self, metadata: impl ToGodot) -> Self {
+        self.metadata = metadata.to_variant();
+        self
+    }
+    pub fn on_selected(mut self, cb: impl Fn(&mut State) + 'static) -> Self {
+        self.on_selected = Some(Box::new(move |state, ()| cb(state)));
+        self
+    }
+    /// Double click or enter on the item.
+    pub fn on_activated(mut self, cb: impl Fn(&mut State) + 'static) -> Self {
+        self.on_activated = Some(Box::new(move |state, ()| cb(state)));
+        self
+    }
+    /// Gets the new text after the user edited it, needs [`editable`](Self::editable).
+    pub fn on_edited(mut self, cb: impl Fn(&mut State, String) + 'static) -> Self {
+        self.on_edited = Some(Box::new(cb));
+        self
+    }
+    /// Gets the new state after the user toggled the [`checkbox`](Self::checkbox).
+    pub fn on_checked(mut self, cb: impl Fn(&mut State, bool) + 'static) -> Self {
+        self.on_checked = Some(Box::new(cb));
+        self
+    }
+
+    fn has_handlers(&self) -> bool {
+        self.on_selected.is_some()
+            || self.on_activated.is_some()
+            || self.on_edited.is_some()
+            || self.on_checked.is_some()
+    }
+}
+
+pub struct TreeItemViewState<ChildViewState> {
+    item: Gd<TreeItem>,
+    routed: bool,
+    child_id: ViewID,
+    child_view_state: ChildViewState,
+}
+
+impl<State: ArgTuple, Children> TreeItemView<State, Children> {
+    fn apply(&self, prev: Option<&Self>, item: &mut Gd<TreeItem>, ctx: &mut Context) {
+        if prev.is_none_or(|prev| prev.checked.is_some() != self.checked.is_some()) {
+            item.set_cell_mode(
+                0,
+                if self.checked.is_some() {
+                    TreeCellMode::CHECK
+                } else {
+                    TreeCellMode::STRING
+                },
+            );
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.text != self.text) {
+            item.set_text(0, &self.text);
+            ctx.stats.attrs_written += 1;
+        }
+        for (column, text) in &self.cells {
+            if prev.is_none_or(|prev| !prev.cells.contains(&(*column, text.clone()))) {
+                item.set_text(*column, text);
+                ctx.stats.attrs_written += 1;
+            }
+        }
+        if let Some(prev) = prev {
+            for (column, _) in &prev.cells {
+                if !self.cells.iter().any(|(c, _)| c == column) {
+                    item.set_text(*column, "");
+                    ctx.stats.attrs_written += 1;
+                }
+            }
+        }
+        if prev.is_none_or(|prev| prev.icon != self.icon) {
+            item.set_icon(0, self.icon.as_ref());
+            ctx.stats.attrs_written += 1;
+        }
+        if let Some(checked) = self.checked
+            && prev.is_none_or(|prev| prev.checked != self.checked)
+        {
+            item.set_checked(0, checked);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.collapsed != self.collapsed) {
+            item.set_collapsed(self.collapsed);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.editable != self.editable) {
+            item.set_editable(0, self.editable);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.selectable != self.selectable) {
+            item.set_selectable(0, self.selectable);
+            ctx.stats.attrs_written += 1;
+        }
+        if prev.is_none_or(|prev| prev.metadata != self.metadata) {
+            item.set_metadata(0, &self.metadata);
+            ctx.stats.attrs_written += 1;
+        }
+    }
+}
+
+impl<State: ArgTuple, Children> View<State> for TreeItemView<State, Children>
+where
+    Children: View<State>,
+{
+    type ViewState = TreeItemViewState<Children::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "TreeItem");
+        let cursor = ctx
+            .tree_cursor
+            .as_mut()
+            .expect("`tree_item` has to be inside the `tree_items` of a `Tree`");
+        let (tree, routes) = (cursor.tree.clone(), cursor.routes.clone());
+        let mut item = cursor.parent.create_child().unwrap();
+        cursor.order.push(item.clone());
+
+        self.apply(None, &mut item, ctx);
+        let routed = self.has_handlers();
+        if routed {
+            let path = ctx.path.clone().into();
+            routes.borrow_mut().insert(item.instance_id(), path);
+        }
+
+        let child_id = ctx.new_structural_id();
+        let child_view_state = ctx.with_tree_cursor(tree, routes, item.clone(), |ctx| {
+            ctx.with_id(child_id, |ctx| {
+                self.children.build(ctx, anchor, anchor_type, app_state)
+            })
+        });
+        TreeItemViewState {
+            item,
+            routed,
+            child_id,
+            child_view_state,
+        }
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "TreeItem");
+        let cursor = ctx
+            .tree_cursor
+            .as_mut()
+            .expect("`tree_item` has to be inside the `tree_items` of a `Tree`");
+        let (tree, routes) = (cursor.tree.clone(), cursor.routes.clone());
+        cursor.order.push(state.item.clone());
+
+        self.apply(Some(prev), &mut state.item, ctx);
+        match (state.routed, self.has_handlers()) {
+            (false, true) => {
+                let path = ctx.path.clone().into();
+                routes.borrow_mut().insert(state.item.instance_id(), path);
+                state.routed = true;
+            }
+            (true, false) => {
+                routes.borrow_mut().remove(&state.item.instance_id());
+                state.routed = false;
+            }
+            _ => {}
+        }
+
+        ctx.with_tree_cursor(tree, routes, state.item.clone(), |ctx| {
+            ctx.with_id(state.child_id, |ctx| {
+                self.children.rebuild(
+                    &prev.children,
+                    &mut state.child_view_state,
+                    ctx,
+                    anchor,
+                    anchor_type,
+                    app_state,
+                );
+            })
+        });
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "TreeItem");
+        let cursor = ctx
+            .tree_cursor
+            .as_ref()
+            .expect("`tree_item` has to be inside the `tree_items` of a `Tree`");
+        let (tree, routes) = (cursor.tree.clone(), cursor.routes.clone());
+        if state.routed {
+            routes.borrow_mut().remove(&state.item.instance_id());
+        }
+        ctx.with_tree_cursor(tree, routes, state.item.clone(), |ctx| {
+            ctx.with_id(state.child_id, |ctx| {
+                self.children.teardown(
+                    &mut state.child_view_state,
+                    ctx,
+                    anchor,
+                    anchor_type,
+                    app_state,
+                );
+            })
+        });
+        state.item.clone().free();
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        match (msg, path.split_first()) {
+            (Message::Signal { name, args }, None) => {
+                match &*name {
+                    "item_selected" => {
+                        if let Some(cb) = &self.on_selected {
+                            cb(app_state, ());
+                        }
+                    }
+                    "item_activated" => {
+                        if let Some(cb) = &self.on_activated {
+                            cb(app_state, ());
+                        }
+                    }
+                    "item_edited" => {
+                        // toggling the checkbox counts as an edit too
+                        if let [text, checked] = &*args {
+                            let checked = checked.to::<bool>();
+                            if let Some(cb) = &self.on_checked
+                                && self.checked.is_some_and(|prev| prev != checked)
+                            {
+                                cb(app_state, checked);
+                            }
+                            let text = text.to::<String>();
+                            if let Some(cb) = &self.on_edited
+                                && text != self.text
+                            {
+                                cb(app_state, text);
+                            }
+                        }
+                    }
+                    _ => return MessageResult::Stale(Message::Signal { name, args }),
+                }
+                MessageResult::Success
+            }
+            (msg, Some((start, rest))) if *start == view_state.child_id => {
+                self.children
+                    .message(msg, rest, &mut view_state.child_view_state, app_state)
+            }
+            (msg, _) => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        self.children.collect_nodes(&state.child_view_state, nodes);
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("TreeItem", &[], |out| {
+            out.with_id(state.child_id, |out| {
+                self.children.inspect(&state.child_view_state, out);
+            });
+        });
+        out.attr("text", &self.text);
+        if let Some(checked) = self.checked {
+            out.attr("checked", checked.to_string());
+        }
+        if state.routed {
+            out.signal("item_selected");
+        }
+    }
+}
+
+/// A `Tree` row showing `text`, see [`TreeItemView`] for the rest.
+pub fn tree_item<State: ArgTuple>(text: impl Into<String>) -> TreeItemView<State, ()> {
+    TreeItemView {
+        text: text.into(),
+        cells: vec![],
+        icon: None,
+        checked: None,
+        collapsed: false,
+        editable: false,
+        selectable: true,
+        metadata: Variant::nil(),
+        children: (),
+        on_selected: None,
+        on_activated: None,
+        on_edited: None,
+        on_checked: None,
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::{ArgTuple, View};
+use crate::{ArgTuple, View, inspect::Inspector};
 
 pub struct Using<State, InnerFn> {
     inner_fn: InnerFn,
@@ -72,6 +72,10 @@ where
     ) {
         state.0.collect_nodes(&state.1, nodes);
     }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        state.0.inspect(&state.1, out);
+    }
 }
 
 pub fn using<State: ArgTuple, InnerFn, Inner>(inner_fn: InnerFn) -> Using<State, InnerFn>
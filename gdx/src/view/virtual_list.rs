@@ -0,0 +1,557 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Range,
+    sync::Arc,
+};
+
+use godot::{
+    builtin::{Callable, Variant, Vector2},
+    classes::{
+        Control, Node, ScrollContainer,
+        control::{LayoutPreset, SizeFlags},
+        scroll_container::ScrollMode,
+    },
+    meta::ToGodot,
+    obj::{Gd, GodotClass, Inherits, NewAlloc},
+};
+
+use crate::{
+    AnchorType, ArgTuple, Context, ElementView, Message, MessageResult, View, ViewID,
+    ctx::FullMessage, inspect::Inspector, trace::trace_span, view::element::impl_element_view,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowHeight {
+    Fixed(f32),
+    /// Rows are measured once built, rows that haven't been built yet count as `estimate`.
+    Measured {
+        estimate: f32,
+    },
+}
+
+/// A `ScrollContainer` (or a subclass) that only builds the rows in view, plus `overscan` rows above and below.
+/// Rows that scroll out are kept around and rebuilt into the rows that scroll in,
+/// rows that stay in view keep their state by key. Keys should be unique, rows sharing a key
+/// get recycled like rows scrolling in.
+pub struct VirtualList<N, State, KeyFn, RowFn> {
+    count: usize,
+    row_height: RowHeight,
+    overscan: usize,
+    key: KeyFn,
+    row: RowFn,
+    _p: PhantomData<(N, State)>,
+}
+
+impl<N, State, KeyFn, RowFn> VirtualList<N, State, KeyFn, RowFn> {
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+}
+
+/// Where each row starts for measured rows, followed by the total height.
+/// Only the rows after the first one whose key or height changed get summed up again.
+struct RowOffsets<K> {
+    keys: Vec<K>,
+    offsets: Vec<f32>,
+    dirty_from: usize,
+}
+
+impl<K: PartialEq> RowOffsets<K> {
+    fn new() -> Self {
+        Self {
+            keys: vec![],
+            offsets: vec![0.0],
+            dirty_from: usize::MAX,
+        }
+    }
+
+    /// Marks the offsets from row `idx` on as outdated.
+    fn invalidate(&mut self, idx: usize) {
+        self.dirty_from = self.dirty_from.min(idx);
+    }
+
+    fn update(&mut self, keys: Vec<K>, height: impl Fn(&K) -> f32) {
+        let same = self
+            .keys
+            .iter()
+            .zip(&keys)
+            .take_while(|(prev, key)| prev == key)
+            .count();
+        let from = self.dirty_from.min(same);
+        self.keys = keys;
+        self.offsets.truncate(from + 1);
+        let mut total = self.offsets[from];
+        for key in &self.keys[from..] {
+            total += height(key);
+            self.offsets.push(total);
+        }
+        self.dirty_from = usize::MAX;
+    }
+}
+
+/// The rows overlapping `top..bottom` plus `overscan` on both ends,
+/// `offset(idx)` being where row `idx` starts and `offset(count)` the total height.
+fn visible_range(
+    count: usize,
+    top: f32,
+    bottom: f32,
+    overscan: usize,
+    offset: impl Fn(usize) -> f32,
+) -> Range<usize> {
+    fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+        let (mut lo, mut hi) = (0, len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pred(mid) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+    let first = partition_point(count, |idx| offset(idx + 1) <= top);
+    let last = partition_point(count, |idx| offset(idx) < bottom);
+    first.saturating_sub(overscan)..(last + overscan).min(count)
+}
+
+struct Slot<K, Row, RowViewState> {
+    // rows get recycled under a different key, so messages are routed by slot instead
+    id: ViewID,
+    key: K,
+    node: Gd<Control>,
+    row: Row,
+    row_view_state: RowViewState,
+}
+
+pub struct VirtualListViewState<N: GodotClass, K, Row, RowViewState> {
+    id: ViewID,
+    node: Gd<N>,
+    scroll: Gd<ScrollContainer>,
+    content: Gd<Control>,
+    callable: Callable,
+    range: Range<usize>,
+    rows: Vec<Slot<K, Row, RowViewState>>,
+    pool: Vec<Slot<K, Row, RowViewState>>,
+    heights: HashMap<K, f32>,
+    offsets: RowOffsets<K>,
+}
+
+impl<N, State: ArgTuple, K, Row, KeyFn, RowFn> VirtualList<N, State, KeyFn, RowFn>
+where
+    N: Inherits<ScrollContainer> + Inherits<Node> + NewAlloc,
+    K: Hash + Eq + Clone,
+    Row: View<State>,
+    KeyFn: Fn(&State, usize) -> K,
+    RowFn: Fn(&State, usize) -> Row,
+{
+    fn offset(&self, offsets: &RowOffsets<K>, idx: usize) -> f32 {
+        match self.row_height {
+            RowHeight::Fixed(height) => idx as f32 * height,
+            RowHeight::Measured { .. } => offsets.offsets[idx],
+        }
+    }
+
+    /// Builds, rebuilds and recycles rows so exactly the ones in view (plus overscan) exist.
+    fn layout(
+        &self,
+        state: &mut VirtualListViewState<N, K, Row, Row::ViewState>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) {
+        // fixed rows can be placed without knowing what's in them, measured ones need every key
+        if let RowHeight::Measured { estimate } = self.row_height {
+            let keys = (0..self.count)
+                .map(|idx| (self.key)(app_state, idx))
+                .collect();
+            let heights = &state.heights;
+            state
+                .offsets
+                .update(keys, |key| heights.get(key).copied().unwrap_or(estimate));
+        }
+        let total = self.offset(&state.offsets, self.count);
+
+        let top = state.scroll.get_v_scroll() as f32;
+        let bottom = top + state.scroll.get_size().y;
+        let range = visible_range(self.count, top, bottom, self.overscan, |idx| {
+            self.offset(&state.offsets, idx)
+        });
+        let keys = range
+            .clone()
+            .map(|idx| match self.row_height {
+                RowHeight::Fixed(_) => (self.key)(app_state, idx),
+                RowHeight::Measured { .. } => state.offsets.keys[idx].clone(),
+            })
+            .collect::<Vec<_>>();
+
+        state
+            .content
+            .set_custom_minimum_size(Vector2::new(0.0, total));
+        let width = state.content.get_size().x;
+
+        // rows that scrolled out are up for recycling into the ones scrolling in,
+        // as are rows whose key showed up twice
+        let visible = keys.iter().collect::<HashSet<_>>();
+        let mut prev_rows = HashMap::new();
+        for mut slot in state.rows.drain(..) {
+            if visible.contains(&slot.key) && !prev_rows.contains_key(&slot.key) {
+                prev_rows.insert(slot.key.clone(), slot);
+            } else {
+                slot.node.set_visible(false);
+                state.pool.push(slot);
+            }
+        }
+        let mut remeasure = false;
+        for (idx, key) in range.clone().zip(&keys) {
+            let (start, end) = (
+                self.offset(&state.offsets, idx),
+                self.offset(&state.offsets, idx + 1),
+            );
+            let row = (self.row)(app_state, idx);
+            let mut slot =
+                if let Some(mut slot) = prev_rows.remove(key).or_else(|| state.pool.pop()) {
+                    ctx.with_id(slot.id, |ctx| {
+                        row.rebuild(
+                            &slot.row,
+                            &mut slot.row_view_state,
+                            ctx,
+                            &mut slot.node.clone().upcast::<Node>(),
+                            AnchorType::ChildOf,
+                            app_state,
+                        );
+                    });
+                    slot.row = row;
+                    slot.key = key.clone();
+                    slot
+                } else {
+                    let mut node = Control::new_alloc();
+                    state.content.add_child(&node);
+                    ctx.stats.nodes_created += 1;
+                    let id = ctx.new_structural_id();
+                    let row_view_state = ctx.with_id(id, |ctx| {
+                        row.build(
+                            ctx,
+                            node.upcast_mut::<Node>(),
+                            AnchorType::ChildOf,
+                            app_state,
+                        )
+                    });
+                    Slot {
+                        id,
+                        key: key.clone(),
+                        node,
+                        row,
+                        row_view_state,
+                    }
+                };
+
+            // the row's controls are stretched over the slot, which is what gets positioned
+            let mut nodes = vec![];
+            slot.row.collect_nodes(&slot.row_view_state, &mut nodes);
+            let mut measured = 0.0f32;
+            for mut control in nodes
+                .into_iter()
+                .filter_map(|node| node.try_cast::<Control>().ok())
+            {
+                control.set_anchors_and_offsets_preset(LayoutPreset::FULL_RECT);
+                measured = measured.max(control.get_combined_minimum_size().y);
+            }
+            if let RowHeight::Measured { .. } = self.row_height
+                && state
+                    .heights
+                    .insert(key.clone(), measured)
+                    .is_none_or(|prev| (prev - measured).abs() > 0.5)
+            {
+                state.offsets.invalidate(idx + 1);
+                remeasure = true;
+            }
+            slot.node.set_visible(true);
+            slot.node.set_position(Vector2::new(0.0, start));
+            slot.node.set_size(Vector2::new(width, end - start));
+            state.rows.push(slot);
+        }
+
+        // keep about a screen's worth of rows for recycling, free the rest
+        while state.pool.len() > state.rows.len() {
+            let mut slot = state.pool.pop().unwrap();
+            self.teardown_slot(&mut slot, state.content.clone(), ctx, app_state);
+        }
+        state.range = range;
+
+        // offsets below a row that measured differently are off, so lay out again
+        if remeasure {
+            ctx.needs_rebuild = true;
+        }
+    }
+
+    fn teardown_slot(
+        &self,
+        slot: &mut Slot<K, Row, Row::ViewState>,
+        mut content: Gd<Control>,
+        ctx: &mut Context,
+        app_state: &mut State,
+    ) {
+        ctx.with_id(slot.id, |ctx| {
+            slot.row.teardown(
+                &mut slot.row_view_state,
+                ctx,
+                &mut slot.node.clone().upcast::<Node>(),
+                AnchorType::ChildOf,
+                app_state,
+            );
+        });
+        // a lingering list keeps its rows until it goes away itself
+        if !ctx.keep_nodes {
+            content.remove_child(&slot.node);
+            slot.node.queue_free();
+        }
+        ctx.stats.nodes_freed += 1;
+    }
+}
+
+impl<N, State: ArgTuple, K, Row, KeyFn, RowFn> View<State> for VirtualList<N, State, KeyFn, RowFn>
+where
+    N: Inherits<ScrollContainer> + Inherits<Node> + NewAlloc,
+    K: Hash + Eq + Clone,
+    Row: View<State>,
+    KeyFn: Fn(&State, usize) -> K,
+    RowFn: Fn(&State, usize) -> Row,
+{
+    type ViewState = VirtualListViewState<N, K, Row, Row::ViewState>;
+
+    fn build(
+        &self,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) -> Self::ViewState {
+        trace_span!(trace_span, "build", view = "VirtualList");
+        let node = N::new_alloc();
+        let mut scroll = node.clone().upcast::<ScrollContainer>();
+        scroll.set_horizontal_scroll_mode(ScrollMode::DISABLED);
+        let mut content = Control::new_alloc();
+        content.set_h_size_flags(SizeFlags::EXPAND_FILL);
+        scroll.add_child(&content);
+        anchor_type.add(anchor, &scroll.clone().upcast());
+        ctx.stats.nodes_created += 2;
+
+        // the visible rows only change when scrolling or resizing, both go through a rebuild
+        let id = ctx.new_structural_id();
+        let msgs = ctx.msg_queue.clone();
+        let path: Arc<[ViewID]> = ctx.with_id(id, |ctx| ctx.path.clone().into());
+        let callable = Callable::from_fn("scroll", move |_| {
+            msgs.lock().push_back(FullMessage {
+                msg: Message::Signal {
+                    name: "scroll".into(),
+                    args: Box::new([]),
+                },
+                path: path.clone(),
+            });
+        });
+        if let Some(mut bar) = scroll.get_v_scroll_bar() {
+            bar.connect("value_changed", &callable);
+        }
+        content.connect("resized", &callable);
+        scroll.connect("resized", &callable);
+        ctx.stats.signals_connected += 3;
+
+        let mut state = VirtualListViewState {
+            id,
+            node,
+            scroll,
+            content,
+            callable,
+            range: 0..0,
+            rows: vec![],
+            pool: vec![],
+            heights: HashMap::new(),
+            offsets: RowOffsets::new(),
+        };
+        self.layout(&mut state, ctx, app_state);
+        state
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        _anchor: &mut Node,
+        _anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "rebuild", view = "VirtualList");
+        self.layout(state, ctx, app_state);
+    }
+
+    fn teardown(
+        &self,
+        state: &mut Self::ViewState,
+        ctx: &mut Context,
+        anchor: &mut Node,
+        anchor_type: AnchorType,
+        app_state: &mut State,
+    ) {
+        trace_span!(trace_span, "teardown", view = "VirtualList");
+        for mut slot in state.rows.drain(..).chain(state.pool.drain(..)) {
+            self.teardown_slot(&mut slot, state.content.clone(), ctx, app_state);
+        }
+        if let Some(mut bar) = state.scroll.get_v_scroll_bar() {
+            bar.disconnect("value_changed", &state.callable);
+        }
+        state.content.disconnect("resized", &state.callable);
+        state.scroll.disconnect("resized", &state.callable);
+        ctx.stats.signals_disconnected += 3;
+        // the content and slot nodes can't go back into the pool, so the list is freed either way
+        if !ctx.keep_nodes {
+            anchor_type.remove(anchor, &state.scroll.clone().upcast());
+            state.scroll.queue_free();
+        }
+        ctx.stats.nodes_freed += 2;
+    }
+
+    fn message(
+        &self,
+        msg: Message,
+        path: &[ViewID],
+        view_state: &mut Self::ViewState,
+        app_state: &mut State,
+    ) -> MessageResult {
+        let Some((start, rest)) = path.split_first() else {
+            return MessageResult::Stale(msg);
+        };
+        if *start == view_state.id {
+            // handling it is enough, the rebuild after it picks the new rows. Scrolling doesn't
+            // change the app state, so it shouldn't get an entry in the history
+            return MessageResult::Untracked;
+        }
+        match view_state.rows.iter_mut().find(|slot| slot.id == *start) {
+            Some(slot) => slot
+                .row
+                .message(msg, rest, &mut slot.row_view_state, app_state),
+            None => MessageResult::Stale(msg),
+        }
+    }
+
+    fn collect_nodes(&self, state: &Self::ViewState, nodes: &mut Vec<Gd<Node>>) {
+        nodes.push(state.scroll.clone().upcast());
+    }
+
+    fn inspect(&self, state: &Self::ViewState, out: &mut Inspector) {
+        out.view("VirtualList", &[state.scroll.clone().upcast()], |out| {
+            for slot in &state.rows {
+                out.with_id(slot.id, |out| slot.row.inspect(&slot.row_view_state, out));
+            }
+        });
+        out.attr(
+            "rows",
+            format!(
+                "{}..{} of {}",
+                state.range.start, state.range.end, self.count
+            ),
+        );
+    }
+}
+
+impl<N, State: ArgTuple, K, Row, KeyFn, RowFn> ElementView<N, State>
+    for VirtualList<N, State, KeyFn, RowFn>
+where
+    N: Inherits<ScrollContainer> + Inherits<Node> + NewAlloc,
+    K: Hash + Eq + Clone,
+    Row: View<State>,
+    KeyFn: Fn(&State, usize) -> K,
+    RowFn: Fn(&State, usize) -> Row,
+{
+    fn get_node(&self, state: &Self::ViewState) -> Gd<N> {
+        state.node.clone()
+    }
+}
+
+impl<N, State0, KeyFn, RowFn> VirtualList<N, State0, KeyFn, RowFn> {
+    impl_element_view! { N }
+}
+
+/// A scrolling list of `count` rows that only builds what's in view, see [`VirtualList`].
+/// `key` identifies the item at an index, `row` builds the view for it.
+pub fn virtual_list<State: ArgTuple, K, Row, KeyFn, RowFn>(
+    count: usize,
+    row_height: RowHeight,
+    key: KeyFn,
+    row: RowFn,
+) -> VirtualList<ScrollContainer, State, KeyFn, RowFn>
+where
+    K: Hash + Eq + Clone,
+    Row: View<State>,
+    KeyFn: Fn(&State, usize) -> K,
+    RowFn: Fn(&State, usize) -> Row,
+{
+    VirtualList {
+        count,
+        row_height,
+        overscan: 3,
+        key,
+        row,
+        _p: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed(count: usize, top: f32, bottom: f32, overscan: usize) -> Range<usize> {
+        visible_range(count, top, bottom, overscan, |idx| idx as f32 * 10.0)
+    }
+
+    #[test]
+    fn range_covers_partially_visible_rows() {
+        assert_eq!(fixed(100, 0.0, 30.0, 0), 0..3);
+        assert_eq!(fixed(100, 5.0, 35.0, 0), 0..4);
+        assert_eq!(fixed(100, 10.0, 30.0, 0), 1..3);
+    }
+
+    #[test]
+    fn range_adds_overscan_within_bounds() {
+        assert_eq!(fixed(100, 50.0, 80.0, 2), 3..10);
+        assert_eq!(fixed(100, 0.0, 30.0, 2), 0..5);
+        assert_eq!(fixed(9, 50.0, 80.0, 2), 3..9);
+    }
+
+    #[test]
+    fn range_of_empty_or_scrolled_past_list() {
+        assert_eq!(fixed(0, 0.0, 30.0, 3), 0..0);
+        assert_eq!(fixed(5, 100.0, 130.0, 0), 5..5);
+    }
+
+    #[test]
+    fn offsets_sum_up_heights() {
+        let mut offsets = RowOffsets::new();
+        offsets.update(vec![1, 2, 3], |key| *key as f32);
+        assert_eq!(offsets.offsets, [0.0, 1.0, 3.0, 6.0]);
+
+        let range = visible_range(3, 2.0, 4.0, 0, |idx| offsets.offsets[idx]);
+        assert_eq!(range, 1..3);
+    }
+
+    #[test]
+    fn offsets_only_redo_changed_rows() {
+        let mut offsets = RowOffsets::new();
+        offsets.update(vec![1, 2, 3], |_| 1.0);
+        // unchanged rows keep their offsets even if their height would differ now
+        offsets.update(vec![1, 2, 4, 5], |_| 2.0);
+        assert_eq!(offsets.offsets, [0.0, 1.0, 2.0, 4.0, 6.0]);
+
+        offsets.invalidate(1);
+        offsets.update(vec![1, 2, 4, 5], |_| 3.0);
+        assert_eq!(offsets.offsets, [0.0, 1.0, 4.0, 7.0, 10.0]);
+
+        offsets.update(vec![1], |_| 3.0);
+        assert_eq!(offsets.offsets, [0.0, 1.0]);
+        offsets.update(vec![], |_| 3.0);
+        assert_eq!(offsets.offsets, [0.0]);
+    }
+}
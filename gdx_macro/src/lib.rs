@@ -28,7 +28,7 @@ pub fn impl_arg_tuple(_item: proc_macro::TokenStream) -> proc_macro::TokenStream
         let value_idents = (0..n)
             .map(|v| Ident::new(&format!("v_{}", v), Span::call_site()))
             .collect::<Vec<_>>();
-        let indices = (0..n).map(|v| Index::from(v)).collect::<Vec<_>>();
+        let indices = (0..n).map(Index::from).collect::<Vec<_>>();
 
         let mut inner = quote! {
             let mut v = ( #(#value_idents,)* );
@@ -66,17 +66,23 @@ pub enum ViewType {
 
 pub struct Event {
     typ: Ident,
-    arg: Option<Ident>,
+    arg: Option<Type>,
 }
 
 pub enum ElemModifier {
     Attr(Ident, Expr, bool),
+    AnimatedAttr(Ident, Expr, Expr),
     Event(Event, Expr),
     ThemeOverride {
         typ: Ident,
         name: Ident,
         value: Expr,
     },
+    Method(
+        Ident,
+        Option<AngleBracketedGenericArguments>,
+        Punctuated<Expr, Token![,]>,
+    ),
 }
 
 impl Parse for MapClause {
@@ -133,7 +139,19 @@ impl Parse for Event {
 
 impl Parse for ElemModifier {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(Token![@]) {
+        if input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            let name = input.parse()?;
+            let generics = if input.peek(Token![::]) {
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            let inner;
+            parenthesized!(inner in input);
+            let args = Punctuated::parse_terminated(&inner)?;
+            Ok(ElemModifier::Method(name, generics, args))
+        } else if input.peek(Token![@]) {
             input.parse::<Token![@]>()?;
             let typ = input.parse()?;
             input.parse::<Token![=]>()?;
@@ -149,6 +167,21 @@ impl Parse for ElemModifier {
             Ok(ElemModifier::ThemeOverride { typ, name, value })
         } else {
             let name = input.parse()?;
+            if input.peek(Token![~]) {
+                input.parse::<Token![~]>()?;
+                // `spec = value` parses as a single assignment expression
+                return match input.parse()? {
+                    Expr::Assign(assign) => Ok(ElemModifier::AnimatedAttr(
+                        name,
+                        *assign.left,
+                        *assign.right,
+                    )),
+                    other => Err(syn::Error::new_spanned(
+                        other,
+                        "expected `name ~ tween_spec = value`",
+                    )),
+                };
+            }
             let build_only = input.peek(Token![@]);
             if build_only {
                 input.parse::<Token![@]>()?;
@@ -371,11 +404,21 @@ impl ViewType {
                         ElemModifier::Attr(ident, expr, build_only) => out.extend(
                             quote! { .attr::<_, _, #build_only>(stringify!(#ident), #expr) },
                         ),
+                        ElemModifier::AnimatedAttr(ident, spec, expr) => {
+                            out.extend(quote! { .animated_attr(stringify!(#ident), #spec, #expr) })
+                        }
                         ElemModifier::Event(event, expr) => {
                             let func_name =
                                 Ident::new(&format!("on_{}", event.typ), event.typ.span());
-                            let arg = event.arg.as_ref().map(|v| quote! { stringify!(#v), });
-                            out.extend(quote! { .#func_name(#arg #expr) })
+                            // `@input: InputEventKey` picks the event type instead of naming something
+                            if event.typ == "input"
+                                && let Some(typ) = &event.arg
+                            {
+                                out.extend(quote! { .#func_name::<#typ, _, _>(#expr) })
+                            } else {
+                                let arg = event.arg.as_ref().map(|v| quote! { stringify!(#v), });
+                                out.extend(quote! { .#func_name(#arg #expr) })
+                            }
                         }
                         ElemModifier::ThemeOverride { typ, name, value } => {
                             let typ = Ident::new(
@@ -386,6 +429,9 @@ impl ViewType {
                                                 quote! { .theme_override::<::gdx::#typ, _>(stringify!(#name), #value) },
                                             )
                         }
+                        ElemModifier::Method(name, generics, args) => {
+                            out.extend(quote! { .#name #generics (#args) })
+                        }
                     }
                 }
                 out